@@ -0,0 +1,145 @@
+use crate::circuit::{precompute_sequence, rows_required};
+use crate::error::Error;
+use crate::prover::{ProofHeader, TranscriptHash};
+use halo2_base::halo2_proofs::{
+    halo2curves::{
+        bn256::{Bn256, Fr, G1Affine},
+        group::{ff::Field, Curve},
+    },
+    plonk::{self, VerifyingKey},
+    poly::{
+        commitment::{Blind, Params},
+        kzg::commitment::ParamsKZG,
+    },
+    transcript::{Blake2bRead, Challenge255, Keccak256Read, TranscriptRead, TranscriptReadBuffer},
+};
+
+// Advice column holding `c` in `FibCircuit`, whose last assigned row is the output
+const OUTPUT_COLUMN: usize = 2;
+
+/// The advice column commitments a `FibCircuit` proof opens with, in column order.
+///
+/// They are the first points the prover writes after the `ProofHeader`, so they can be read
+/// off without verifying, with the hash the header names. The prover fills the blinding rows
+/// with fresh randomness, so two proofs of the same witness carry different commitments; bind
+/// to the one in the proof being relayed.
+pub fn advice_commitments(
+    vk: &VerifyingKey<G1Affine>,
+    proof: &[u8],
+) -> Result<Vec<G1Affine>, Error> {
+    let (header, transcript) = ProofHeader::parse(proof)?;
+    match header.transcript {
+        TranscriptHash::Blake2b => read_advice::<Blake2bRead<_, _, _>>(vk, transcript),
+        TranscriptHash::Keccak256 => read_advice::<Keccak256Read<_, _, _>>(vk, transcript),
+    }
+}
+
+fn read_advice<'proof, T>(
+    vk: &VerifyingKey<G1Affine>,
+    transcript: &'proof [u8],
+) -> Result<Vec<G1Affine>, Error>
+where
+    T: TranscriptReadBuffer<&'proof [u8], G1Affine, Challenge255<G1Affine>>,
+{
+    let mut transcript = T::init(transcript);
+
+    (0..vk.cs().num_advice_columns())
+        .map(|_| {
+            transcript
+                .read_point()
+                .map_err(|err| plonk::Error::Transcript(err).into())
+        })
+        .collect()
+}
+
+/// Commitment to the column carrying the output, as it appears in `proof`
+pub fn output_commitment(vk: &VerifyingKey<G1Affine>, proof: &[u8]) -> Result<G1Affine, Error> {
+    advice_commitments(vk, proof)?
+        .get(OUTPUT_COLUMN)
+        .copied()
+        .ok_or_else(|| plonk::Error::BoundsFailure.into())
+}
+
+/// Unblinded commitment to the output column recomputed from the seeds, identical for every
+/// proof of the same witness. It matches the column as assigned, with zeros where a proof
+/// would put its blinding rows.
+pub fn output_column_commitment(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    fib_size: usize,
+    seeds: (Fr, Fr),
+) -> G1Affine {
+    let (a, b) = seeds;
+    let domain = vk.get_domain();
+    let mut column = vec![Fr::zero(); params.n() as usize];
+
+    // Row i holds F_{i+3}
+    let seq = precompute_sequence(fib_size, a, b);
+    for (row, value) in seq.iter().skip(2).take(rows_required(fib_size)).enumerate() {
+        column[row] = *value;
+    }
+
+    params
+        .commit_lagrange(&domain.lagrange_from_vec(column), Blind::default())
+        .to_affine()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::FibCircuit;
+    use crate::prover::{gen_params, gen_pk, min_k_for, prove_fib, prove_fib_with_transcript};
+    use halo2_base::halo2_proofs::{
+        halo2curves::group::GroupEncoding, poly::kzg::multiopen::ProverSHPLONK,
+        transcript::Keccak256Write,
+    };
+
+    #[test]
+    fn output_commitments() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let seeds = (Fr::from(1), Fr::from(1));
+        let instances = vec![vec![seeds.0, seeds.1]];
+
+        let first = prove_fib(&params, &pk, circuit.clone(), &instances, false).unwrap();
+        let second = prove_fib(&params, &pk, circuit.clone(), &instances, false).unwrap();
+
+        let recomputed = output_column_commitment(&params, pk.get_vk(), 10, seeds);
+        assert_eq!(
+            recomputed,
+            output_column_commitment(&params, pk.get_vk(), 10, seeds)
+        );
+        assert_ne!(
+            recomputed,
+            output_column_commitment(&params, pk.get_vk(), 10, (seeds.0, Fr::from(2)))
+        );
+
+        // Read twice from one proof it is the same point; fresh blinding moves it between proofs
+        let committed = output_commitment(pk.get_vk(), &first).unwrap();
+        assert_eq!(committed, output_commitment(pk.get_vk(), &first).unwrap());
+        assert_ne!(committed, output_commitment(pk.get_vk(), &second).unwrap());
+        assert_eq!(advice_commitments(pk.get_vk(), &first).unwrap().len(), 3);
+
+        // The first commitment is the first point after the header, under either hash
+        let keccak = prove_fib_with_transcript::<
+            ProverSHPLONK<'_, Bn256>,
+            Keccak256Write<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+            _,
+        >(&params, &pk, circuit, &instances, false)
+        .unwrap();
+        for proof in [&first, &keccak] {
+            let point = advice_commitments(pk.get_vk(), proof).unwrap()[0].to_bytes();
+            let len = point.as_ref().len();
+            assert_eq!(
+                point.as_ref(),
+                &proof[ProofHeader::LEN..ProofHeader::LEN + len]
+            );
+        }
+
+        assert!(output_commitment(pk.get_vk(), &first[..40]).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use crate::circuit::FibCircuit;
+use crate::encoding::{encode_instance, Endianness};
+use crate::error::Error;
+use crate::prover::verify_fib;
+use crate::spec::CircuitSpec;
+use halo2_base::halo2_proofs::{
+    halo2curves::{
+        bn256::{Bn256, Fr, G1Affine},
+        group::ff::PrimeField,
+    },
+    plonk::{keygen_vk, VerifyingKey},
+    poly::kzg::commitment::ParamsKZG,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+type Key = [u8; 32];
+
+/// Remembers verification results for `(instance, proof)` pairs under one verifying key.
+///
+/// The key's transcript representation is hashed into every entry as well, so results can
+/// never leak between verifiers built for different circuits.
+pub struct CachingVerifier<'a> {
+    params: &'a ParamsKZG<Bn256>,
+    vk: &'a VerifyingKey<G1Affine>,
+    capacity: usize,
+    results: HashMap<Key, bool>,
+    // Least recently used at the front
+    order: VecDeque<Key>,
+    hits: usize,
+}
+
+impl<'a> CachingVerifier<'a> {
+    pub fn new(
+        params: &'a ParamsKZG<Bn256>,
+        vk: &'a VerifyingKey<G1Affine>,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            params,
+            vk,
+            capacity,
+            results: HashMap::new(),
+            order: VecDeque::new(),
+            hits: 0,
+        }
+    }
+
+    pub fn verify(&mut self, instances: &[Vec<Fr>], proof: &[u8]) -> bool {
+        let key = self.key(instances, proof);
+
+        if let Some(&ok) = self.results.get(&key) {
+            self.hits += 1;
+            self.touch(key);
+            return ok;
+        }
+
+        let ok = verify_fib(self.params, self.vk, instances, proof).is_ok();
+        if self.capacity > 0 {
+            if self.results.len() == self.capacity {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.results.remove(&oldest);
+                }
+            }
+            self.results.insert(key, ok);
+            self.order.push_back(key);
+        }
+        ok
+    }
+
+    /// Number of verifications answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    fn touch(&mut self, key: Key) {
+        if let Some(pos) = self.order.iter().position(|k| *k == key) {
+            self.order.remove(pos);
+        }
+        self.order.push_back(key);
+    }
+
+    fn key(&self, instances: &[Vec<Fr>], proof: &[u8]) -> Key {
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .to_state()
+            .update(self.vk.transcript_repr().to_repr().as_ref())
+            .update(&encode_instance(instances, Endianness::Little))
+            .update(proof)
+            .finalize();
+
+        let mut key = [0u8; 32];
+        key.copy_from_slice(hash.as_bytes());
+        key
+    }
+}
+
+type VkSlot = Arc<Mutex<Option<Arc<VerifyingKey<G1Affine>>>>>;
+
+/// Verifying keys generated on first request for each `CircuitSpec` and shared after that.
+///
+/// Every key comes from the one `params`, which must have rows for the largest spec asked
+/// for. The map lock is only held to find a spec's slot; keygen runs under that slot's own
+/// lock, so concurrent requests for one spec wait for a single generation while other specs
+/// go ahead. A failed keygen leaves the slot empty for the next request to retry.
+pub struct VkCache<'a> {
+    params: &'a ParamsKZG<Bn256>,
+    slots: Mutex<HashMap<CircuitSpec, VkSlot>>,
+    generated: AtomicUsize,
+}
+
+impl<'a> VkCache<'a> {
+    pub fn new(params: &'a ParamsKZG<Bn256>) -> Self {
+        Self {
+            params,
+            slots: Mutex::new(HashMap::new()),
+            generated: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn get(&self, spec: &CircuitSpec) -> Result<Arc<VerifyingKey<G1Affine>>, Error> {
+        let slot = self
+            .slots
+            .lock()
+            .unwrap()
+            .entry(spec.clone())
+            .or_default()
+            .clone();
+
+        let mut vk = slot.lock().unwrap();
+        if let Some(vk) = vk.as_ref() {
+            return Ok(vk.clone());
+        }
+
+        let circuit = FibCircuit::from_spec(spec)?;
+        let generated = Arc::new(keygen_vk(self.params, &circuit)?);
+        self.generated.fetch_add(1, Ordering::Relaxed);
+        *vk = Some(generated.clone());
+        Ok(generated)
+    }
+
+    /// Number of keys generated rather than served from the cache
+    pub fn generated(&self) -> usize {
+        self.generated.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prover::{gen_params, gen_pk, min_k_for, prove_fib};
+
+    #[test]
+    fn repeat_verification_hits_cache() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+
+        let mut verifier = CachingVerifier::new(&params, pk.get_vk(), 4);
+        assert!(verifier.verify(&instances, &proof));
+        assert_eq!(verifier.hits(), 0);
+        assert!(verifier.verify(&instances, &proof));
+        assert_eq!(verifier.hits(), 1);
+
+        let other = vec![vec![Fr::from(2), Fr::from(1)]];
+        assert!(!verifier.verify(&other, &proof));
+        assert_eq!(verifier.hits(), 1);
+    }
+
+    #[test]
+    fn same_spec_reuses_vk() {
+        let params = gen_params(min_k_for(20));
+        let cache = VkCache::new(&params);
+
+        let spec = CircuitSpec::fibonacci(10);
+        let first = cache.get(&spec).unwrap();
+        let second = cache.get(&CircuitSpec::fibonacci(10)).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.generated(), 1);
+
+        // Racing requests for a new spec still generate it once
+        let spec = CircuitSpec::fibonacci(20);
+        let vks: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| cache.get(&spec).unwrap()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert!(vks.iter().all(|vk| Arc::ptr_eq(vk, &vks[0])));
+        assert_eq!(cache.generated(), 2);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::digits::{DigitsChip, DigitsConfig};
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b, ceiling]
+const CEILING_ROW: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct CeilingConfig {
+    pub fib: FibConfig,
+    pub digits: DigitsConfig,
+    /// The value, the ceiling and the gap `ceiling - value - 1` in one row
+    pub terms: [Column<Advice>; 3],
+    pub selector: Selector,
+}
+
+/// Proves a private value is below a public ceiling by range-checking the gap
+/// `ceiling - value - 1` with `DigitsChip`.
+///
+/// When the value reaches the ceiling the gap wraps to `p - (value - ceiling + 1)`, which has
+/// far more decimal digits than the check allows. That holds as long as 10^num_digits stays
+/// below `p - ceiling`, so `num_digits` should be no larger than the ceilings in use need.
+pub struct CeilingChip<F: FieldExt> {
+    config: CeilingConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CeilingChip<F> {
+    pub fn construct(config: CeilingConfig) -> CeilingChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_ceiling(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> CeilingConfig {
+        let fib = FibChip::configure(advice, instance, cs);
+        let digits = DigitsChip::configure_digits(cs);
+        let selector = cs.selector();
+
+        // Reuses the chain columns, which already take copies
+        cs.create_gate("ceiling gap", |cells| {
+            let s = cells.query_selector(selector);
+            let [value, ceiling, gap] =
+                advice.map(|column| cells.query_advice(column, Rotation::cur()));
+
+            vec![s * (ceiling - value - Expression::Constant(F::one()) - gap)]
+        });
+
+        CeilingConfig {
+            fib,
+            digits,
+            terms: advice,
+            selector,
+        }
+    }
+
+    pub fn load_table(&self, layouter: impl Layouter<F>) -> Result<(), Error> {
+        DigitsChip::construct(self.config.digits.clone()).load_table(layouter)
+    }
+
+    /// Constrains `value` below the ceiling at `ceiling_row` of the instance, with the gap
+    /// checked to fit in `num_digits` decimal digits
+    pub fn assign_below(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: &AssignedCell<F, F>,
+        ceiling_row: usize,
+        num_digits: usize,
+    ) -> Result<(), Error> {
+        let config = &self.config;
+        let [col_value, col_ceiling, col_gap] = config.terms;
+
+        let gap = layouter.assign_region(
+            || "ceiling",
+            |mut region| {
+                config.selector.enable(&mut region, 0)?;
+
+                value.copy_advice(|| "value", &mut region, col_value, 0)?;
+                let ceiling = region.assign_advice_from_instance(
+                    || "ceiling",
+                    config.fib.instance,
+                    ceiling_row,
+                    col_ceiling,
+                    0,
+                )?;
+
+                let gap = ceiling.value().copied() - value.value() - Value::known(F::one());
+                region.assign_advice(|| "gap", col_gap, 0, || gap)
+            },
+        )?;
+
+        DigitsChip::construct(config.digits.clone()).assign_digits(
+            layouter.namespace(|| "gap digits"),
+            &gap,
+            num_digits,
+        )?;
+        Ok(())
+    }
+}
+
+/// Proves F_{fib_size} is below the ceiling at instance row 2 without exposing it
+#[derive(Clone, Debug, Default)]
+pub struct CeilingCircuit {
+    pub fib_size: usize,
+    pub num_digits: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for CeilingCircuit {
+    type Config = CeilingConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        CeilingChip::configure_ceiling(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        let fib = FibChip::construct(config.fib.clone());
+        let (_, mut b, mut c) = fib.assign_first_row(layouter.namespace(|| "first row"))?;
+        for _ in 3..self.fib_size {
+            let new_c = fib.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            b = c;
+            c = new_c;
+        }
+
+        let chip = CeilingChip::construct(config);
+        chip.load_table(layouter.namespace(|| "digit table"))?;
+        chip.assign_below(
+            layouter.namespace(|| "below ceiling"),
+            &c,
+            CEILING_ROW,
+            self.num_digits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn f_10_below_ceiling() {
+        let circuit = CeilingCircuit {
+            fib_size: 10,
+            num_digits: 20,
+        };
+        let one = Fr::from(1);
+
+        // F_10 = 55
+        let prover =
+            MockProver::<Fr>::run(6, &circuit, vec![vec![one, one, Fr::from(100)]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let prover =
+            MockProver::<Fr>::run(6, &circuit, vec![vec![one, one, Fr::from(50)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // The bound is strict
+        let prover =
+            MockProver::<Fr>::run(6, &circuit, vec![vec![one, one, Fr::from(55)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,671 @@
+use crate::error;
+use crate::export::to_biguint;
+use crate::source::WitnessSource;
+use crate::{FibChip, FibConfig, InstanceLayout};
+use halo2_base::halo2_proofs::{
+    circuit::*,
+    halo2curves::{bn256::Fr, FieldExt},
+    plonk::*,
+};
+use num_bigint::BigUint;
+#[cfg(feature = "prover")]
+use rayon::prelude::*;
+
+// Logical instance rows read or written by `FibCircuit`: the seeds, then the optional output
+pub(crate) const SEED_ROWS: [usize; 2] = [0, 1];
+pub(crate) const OUTPUT_ROW: usize = 2;
+// With a `WitnessSource` the seeds stay private and the output moves up to the first row
+pub(crate) const PRIVATE_OUTPUT_ROW: usize = 0;
+
+// Below this a thread costs more than the additions it would take over
+#[cfg(feature = "prover")]
+const MIN_PAR_CHUNK: usize = 1 << 12;
+
+// Past F_70 or so the float itself is off by more than one, so `binet_check` goes relative
+const BINET_TOLERANCE: f64 = 1e-12;
+
+/// Rows taken by a `fib_size` sequence: one region for the first row, then one per step
+pub fn rows_required(fib_size: usize) -> usize {
+    fib_size.saturating_sub(2).max(1)
+}
+
+/// Off-circuit reference for the final `c` that `FibCircuit` assigns, i.e. F_{fib_size}.
+/// Only field arithmetic, so it holds for any `FieldExt` whatever its size or limb layout.
+pub fn compute<F: FieldExt>(fib_size: usize, a: F, b: F) -> F {
+    let (mut b, mut c) = (b, a + b);
+    for _ in 3..fib_size {
+        let next = b + c;
+        b = c;
+        c = next;
+    }
+    c
+}
+
+/// Every term F_1..=F_{fib_size} for seeds (a, b), allocated once up front
+pub fn precompute_sequence<F: FieldExt>(fib_size: usize, a: F, b: F) -> Vec<F> {
+    let mut seq = vec![F::zero(); fib_size];
+    fill_sequence(&mut seq, a, b);
+    seq
+}
+
+/// Same output as `precompute_sequence`, split across the rayon pool.
+///
+/// The recurrence itself is sequential, but each chunk can find its own starting pair with
+/// the doubling identities in O(log n) and then fill its slice independently.
+#[cfg(feature = "prover")]
+pub fn precompute_sequence_par<F: FieldExt>(fib_size: usize, a: F, b: F) -> Vec<F> {
+    let mut seq = vec![F::zero(); fib_size];
+    let chunk = (fib_size / rayon::current_num_threads()).max(MIN_PAR_CHUNK);
+
+    seq.par_chunks_mut(chunk).enumerate().for_each(|(i, out)| {
+        let (x, y) = advance(a, b, i * chunk);
+        fill_sequence(out, x, y);
+    });
+    seq
+}
+
+fn fill_sequence<F: FieldExt>(out: &mut [F], a: F, b: F) {
+    let (mut x, mut y) = (a, b);
+    for slot in out {
+        *slot = x;
+        let next = x + y;
+        x = y;
+        y = next;
+    }
+}
+
+/// (s_n, s_{n+1}) where s_0 = a, s_1 = b, using s_n = a F_{n-1} + b F_n
+#[cfg(feature = "prover")]
+fn advance<F: FieldExt>(a: F, b: F, n: usize) -> (F, F) {
+    let (f_n, f_n1) = fib_pair::<F>(n);
+    let f_nm1 = f_n1 - f_n;
+    (a * f_nm1 + b * f_n, a * f_n + b * f_n1)
+}
+
+/// (F_n, F_{n+1}) with F_0 = 0 by fast doubling
+#[cfg(feature = "prover")]
+fn fib_pair<F: FieldExt>(n: usize) -> (F, F) {
+    let mut pair = (F::zero(), F::one());
+    for bit in (0..usize::BITS - n.leading_zeros()).rev() {
+        let (f, g) = pair;
+        // F_{2k} = F_k (2 F_{k+1} - F_k), F_{2k+1} = F_k^2 + F_{k+1}^2
+        let even = f * (g.double() - f);
+        let odd = f.square() + g.square();
+        pair = if (n >> bit) & 1 == 1 {
+            (odd, even + odd)
+        } else {
+            (even, odd)
+        };
+    }
+    pair
+}
+
+/// Compares `value`, read as an integer, against the rounded Binet formula phi^n / sqrt(5).
+///
+/// A cheap test oracle for F_n with seeds (1, 1), not a proof: it is exact while F_n fits an
+/// f64 mantissa, only catches gross errors beyond that, and means nothing once F_n wraps the
+/// modulus (around n = 367 for bn256).
+pub fn binet_check<F: FieldExt>(n: usize, value: F) -> bool {
+    let sqrt5 = 5f64.sqrt();
+    let approx = ((1.0 + sqrt5) / 2.0).powi(n as i32) / sqrt5;
+    // The repr is little-endian, so fold from the most significant byte down
+    let repr = value.to_repr();
+    let value = repr
+        .as_ref()
+        .iter()
+        .rev()
+        .fold(0f64, |acc, byte| acc * 256.0 + f64::from(*byte));
+
+    (value - approx).abs() <= (approx * BINET_TOLERANCE).max(0.5)
+}
+
+/// Largest n with the integer F_n, from seeds (1, 1), still below the modulus of `F`: 366
+/// for the bn256 scalar field. Past it the circuit's terms are residues, not the integers.
+pub fn max_unwrapped_index<F: FieldExt>() -> usize {
+    let modulus = to_biguint(-F::one()) + 1u32;
+    let (mut a, mut b) = (BigUint::from(1u32), BigUint::from(1u32));
+    let mut n = 2;
+    while b < modulus {
+        let next = &a + &b;
+        a = std::mem::replace(&mut b, next);
+        n += 1;
+    }
+    n - 1
+}
+
+/// Where `FibCircuit` takes its seeds from when it has no `WitnessSource`: instance rows 0
+/// and 1. Uninhabited, so a `FibCircuit<InstanceSeeds>` always has `source: None`.
+#[derive(Clone, Copy, Debug)]
+pub enum InstanceSeeds {}
+
+impl<F> WitnessSource<F> for InstanceSeeds {
+    fn seeds(&self) -> (F, F) {
+        match *self {}
+    }
+
+    fn size(&self) -> usize {
+        match *self {}
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FibCircuit<S = InstanceSeeds> {
+    pub fib_size: usize,
+    /// Constrain the final term to the output row, after the seeds by default
+    pub expose_output: bool,
+    pub instance_layout: InstanceLayout,
+    /// Private seeds pulled at synthesis time. With `None` they are read from the instance;
+    /// with a source only the output is public, at instance row 0.
+    pub source: Option<S>,
+}
+
+// Not derived: a derive would be generic over `S`, and `FibCircuit { .., ..Default::default() }`
+// could then no longer infer the default source
+impl Default for FibCircuit {
+    fn default() -> Self {
+        Self {
+            fib_size: 0,
+            expose_output: false,
+            instance_layout: InstanceLayout::default(),
+            source: None,
+        }
+    }
+}
+
+impl<S> FibCircuit<S> {
+    /// A circuit driven by `source`, sized by it and exposing only the final term
+    pub fn from_source<F>(source: S) -> Self
+    where
+        S: WitnessSource<F>,
+    {
+        Self {
+            fib_size: source.size(),
+            expose_output: true,
+            instance_layout: InstanceLayout::default(),
+            source: Some(source),
+        }
+    }
+
+    fn output_row(&self) -> usize {
+        if self.source.is_some() {
+            PRIVATE_OUTPUT_ROW
+        } else {
+            OUTPUT_ROW
+        }
+    }
+
+    /// Checks that every instance row the circuit reads or exposes exists in `instances`.
+    ///
+    /// `expose_public` cannot do this itself: the chip never sees the instance values, and a
+    /// row past their end is silently zero-padded, so the mismatch would otherwise surface
+    /// only as an unsatisfied copy constraint.
+    pub fn check_instances(&self, instances: &[Vec<Fr>]) -> Result<(), error::Error> {
+        self.check_instance_len(instances.first().map_or(0, Vec::len))
+    }
+
+    /// `check_instances` for an instance column of `len` rows
+    pub(crate) fn check_instance_len(&self, len: usize) -> Result<(), error::Error> {
+        let seeds: &[usize] = if self.source.is_some() {
+            &[]
+        } else {
+            &SEED_ROWS
+        };
+        let output = self.expose_output.then_some(self.output_row());
+
+        let rows = seeds.iter().copied().chain(output);
+        match rows
+            .map(|row| self.instance_layout.row(row))
+            .find(|row| *row >= len)
+        {
+            Some(row) => Err(error::Error::InstanceIndexOutOfRange { row, len }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<F: FieldExt, S: WitnessSource<F> + Clone> Circuit<F> for FibCircuit<S> {
+    type Config = FibConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    // Every field but `source` is shape: `fib_size` sets the row count and `expose_output` the
+    // copy constraints, and both must survive into keygen. The source only supplies values,
+    // which keygen never reads, so it is kept rather than needing `S: Default`.
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    // Circuit setup (doesn't change on input)
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let col_a = cs.advice_column();
+        let col_b = cs.advice_column();
+        let col_c = cs.advice_column();
+        let instance = cs.instance_column();
+
+        FibChip::configure([col_a, col_b, col_c], instance, cs)
+    }
+
+    // Changes for each proof
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // The first row already produces the third term, so anything shorter can't be laid out
+        if self.fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        // Outside the regions, but still once per synthesis: keygen and proving both warn
+        if self.fib_size > max_unwrapped_index::<F>() {
+            log::warn!(
+                "fib_size = {} passes the field modulus, so F_n is exposed reduced",
+                self.fib_size
+            );
+        }
+
+        let advice = config.advice;
+        let chip = FibChip::construct_with_layout(config, self.instance_layout.clone());
+
+        let (_, mut b, mut c) = match &self.source {
+            None => chip.assign_first_row(layouter.namespace(|| "first row"))?,
+            Some(source) => {
+                let (a, b) = source.seeds();
+                let (a, b) = layouter.assign_region(
+                    || "seeds",
+                    |mut region| {
+                        let a = region.assign_advice(|| "a", advice[0], 0, || Value::known(a))?;
+                        let b = region.assign_advice(|| "b", advice[1], 0, || Value::known(b))?;
+                        Ok((a, b))
+                    },
+                )?;
+                chip.assign_first_row_from_cells(layouter.namespace(|| "first row"), &a, &b)?
+            }
+        };
+
+        // We've skipped the first 2 items in fib sequence (as they are awkward)
+        // 0 should be 3, just using 0 to test row count - 57 should be =9
+        for _ in 3..self.fib_size {
+            let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            b = c;
+            c = new_c;
+        }
+        chip.check_selector_enabled(rows_required(self.fib_size))?;
+
+        log::debug!("c: {:?}", c.value());
+
+        chip.try_expose_public(
+            layouter.namespace(|| "out"),
+            &c,
+            self.expose_output.then_some(self.output_row()),
+        )
+    }
+}
+
+/// Exposes the last two terms (F_{fib_size}, F_{fib_size - 1}) at instance rows 2 and 3, for
+/// golden-ratio convergents computed off-circuit
+#[derive(Clone, Debug, Default)]
+pub struct ConvergentCircuit {
+    pub fib_size: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for ConvergentCircuit {
+    type Config = FibConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        FibChip::configure(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        let chip = FibChip::construct(config);
+        let (_, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+        for _ in 3..self.fib_size {
+            let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            b = c;
+            c = new_c;
+        }
+
+        chip.expose_pair(layouter.namespace(|| "pair"), &c, &b, OUTPUT_ROW)
+    }
+}
+
+/// Exposes every `stride`-th term, F_s, F_2s, ... up to F_{fib_size}, at instance rows 2..
+/// after the seeds. Every term is still assigned and constrained; the stride only decides
+/// which ones an auditor gets to see.
+#[derive(Clone, Debug, Default)]
+pub struct StridedCircuit {
+    pub fib_size: usize,
+    pub stride: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for StridedCircuit {
+    type Config = FibConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        FibChip::configure(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.fib_size < 3 || self.stride == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        let chip = FibChip::construct(config);
+        let (a, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+
+        // terms[i] is the cell holding F_{i+1}
+        let mut terms = vec![a, b.clone(), c.clone()];
+        for _ in 3..self.fib_size {
+            let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            terms.push(new_c.clone());
+            b = c;
+            c = new_c;
+        }
+
+        let checkpoints = terms.iter().skip(self.stride - 1).step_by(self.stride);
+        for (i, term) in checkpoints.enumerate() {
+            chip.expose_public(layouter.namespace(|| "checkpoint"), term, OUTPUT_ROW + i)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Proves the sum of the first `n` terms, exposed as instance row 2
+#[derive(Clone, Debug, Default)]
+pub struct SumCircuit {
+    pub n: usize,
+    pub check_identity: bool,
+}
+
+impl<F: FieldExt> Circuit<F> for SumCircuit {
+    type Config = FibConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let col_a = cs.advice_column();
+        let col_b = cs.advice_column();
+        let col_c = cs.advice_column();
+        let sum = cs.advice_column();
+        let instance = cs.instance_column();
+
+        FibChip::configure_with_sum([col_a, col_b, col_c], sum, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FibChip::construct(config);
+
+        let total =
+            chip.assign_with_sum(layouter.namespace(|| "sum"), self.n, self.check_identity)?;
+        chip.expose_public(layouter.namespace(|| "total"), &total, 2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::{
+        bn256::{self, Fr},
+        pasta,
+    };
+
+    // (n, seed_a, seed_b, s_n mod p) for the scalar fields of bn256, grumpkin (bn256's base
+    // field) and pallas, reduced from the exact integers with arbitrary-precision arithmetic.
+    // From n = 370 the terms exceed every modulus, so the fields disagree.
+    const VECTORS: &[(usize, u64, u64, [&str; 3])] = &[
+        (3, 1, 1, ["2", "2", "2"]),
+        (10, 1, 1, ["55", "55", "55"]),
+        (
+            100,
+            1,
+            1,
+            [
+                "354224848179261915075",
+                "354224848179261915075",
+                "354224848179261915075",
+            ],
+        ),
+        (
+            370,
+            1,
+            1,
+            [
+                "7058084608948737124309748592735156172243725160565728232527801574298579272067",
+                "7058084608948737124309748592735156171651938133038570956564466742020908420203",
+                "7766989168318691445617132817248325636348013316404923468281390075421724410244",
+            ],
+        ),
+        (
+            500,
+            2,
+            7,
+            [
+                "5622154551366957932479150233319596849543289331726766639750682174441242001284",
+                "5622154546570043677091757627800080589319265855466212972797153538544046295626",
+                "10242369774950277421753956314370756390537266900854744981422000156992457950673",
+            ],
+        ),
+        (
+            1000,
+            3,
+            5,
+            [
+                "20803532298680847025916140148304892827382052422880962339972458798947487818390",
+                "16638540580333462403469044046404862748095596825089255249514726530734678279757",
+                "3664643044456465060955663269205631673822972476039273116003130252524991102049",
+            ],
+        ),
+        (
+            4096,
+            1,
+            1,
+            [
+                "7077190736973415298668584622861610933169111293014881987569797983110722008198",
+                "9870045679424970195334658750053746651646886551556334294720863995372973063113",
+                "10116329963254105525431199660791995488949082592015660292443929202822423645400",
+            ],
+        ),
+    ];
+
+    fn check_vectors<F: FieldExt>(field: usize) {
+        for (n, a, b, expected) in VECTORS {
+            let expected = F::from_str_vartime(expected[field]).unwrap();
+            let (a, b) = (F::from(*a), F::from(*b));
+
+            assert_eq!(compute(*n, a, b), expected, "compute, n = {n}");
+            assert_eq!(
+                precompute_sequence(*n, a, b)[n - 1],
+                expected,
+                "precompute, n = {n}"
+            );
+            assert_eq!(
+                precompute_sequence_par(*n, a, b)[n - 1],
+                expected,
+                "parallel, n = {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn sum_of_first_ten() {
+        // 1 + 1 + 2 + 3 + 5 + 8 + 13 + 21 + 34 + 55 = 143 = F_12 - 1
+        let circuit = SumCircuit {
+            n: 10,
+            check_identity: true,
+        };
+
+        let instance = vec![Fr::from(1), Fr::from(1), Fr::from(143)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let instance = vec![Fr::from(1), Fr::from(1), Fr::from(142)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn convergent_pair() {
+        let circuit = ConvergentCircuit { fib_size: 10 };
+        let one = Fr::from(1);
+
+        // 55 / 34 ~ 1.6176
+        let instance = vec![one, one, Fr::from(55), Fr::from(34)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let instance = vec![one, one, Fr::from(34), Fr::from(55)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn stride_checkpoints() {
+        let circuit = StridedCircuit {
+            fib_size: 20,
+            stride: 5,
+        };
+        let one = Fr::from(1);
+
+        // F_5, F_10, F_15, F_20
+        let checkpoints = [5u64, 55, 610, 6765].map(Fr::from);
+        let instance = [vec![one, one], checkpoints.to_vec()].concat();
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance.clone()]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let mut tampered = instance;
+        tampered[4] = Fr::from(611);
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![tampered]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn parallel_precompute_matches_serial() {
+        let (a, b) = (Fr::from(3), Fr::from(7));
+
+        for fib_size in [0, 1, 2, 3, 10, MIN_PAR_CHUNK * 3 + 17] {
+            let serial = precompute_sequence(fib_size, a, b);
+            assert_eq!(serial.len(), fib_size);
+            assert_eq!(precompute_sequence_par(fib_size, a, b), serial);
+            if fib_size >= 3 {
+                assert_eq!(serial[fib_size - 1], compute(fib_size, a, b));
+            }
+        }
+
+        let seq = precompute_sequence(10, Fr::from(1), Fr::from(1));
+        assert_eq!(seq[9], Fr::from(55));
+    }
+
+    #[test]
+    fn binet_oracle() {
+        let one = Fr::from(1);
+        // `compute` starts at the third term, like the circuit, so 1 and 2 both give a + b
+        for n in 3..=300 {
+            assert!(binet_check(n, compute(n, one, one)), "n = {n}");
+        }
+
+        // Off by one is caught while the float is exact, a doubled term at any size
+        for n in 3..=50 {
+            assert!(!binet_check(n, compute(n, one, one) + one), "n = {n}");
+        }
+        assert!(!binet_check(300, compute(300, one, one).double()));
+    }
+
+    #[test]
+    #[ignore]
+    fn precompute_timing() {
+        let (a, b) = (Fr::from(1), Fr::from(1));
+        let fib_size = 1 << 22;
+
+        let start = std::time::Instant::now();
+        let serial = precompute_sequence(fib_size, a, b);
+        let serial_time = start.elapsed();
+
+        let start = std::time::Instant::now();
+        let parallel = precompute_sequence_par(fib_size, a, b);
+        let parallel_time = start.elapsed();
+
+        assert_eq!(serial, parallel);
+        println!("serial: {serial_time:?}, parallel: {parallel_time:?}");
+    }
+
+    #[test]
+    fn conformance_vectors() {
+        check_vectors::<bn256::Fr>(0);
+        check_vectors::<bn256::Fq>(1);
+        check_vectors::<pasta::Fq>(2);
+    }
+
+    // First n where F_n, read as an integer, drops below F_{n-1}: the sum wrapped past p
+    fn wrap_index<F: FieldExt>() -> usize {
+        let as_int = |value: F| {
+            let mut bytes = value.to_repr().as_ref().to_vec();
+            bytes.reverse();
+            bytes
+        };
+        let seq = precompute_sequence(1000, F::one(), F::one());
+
+        (2..seq.len())
+            .find(|i| as_int(seq[*i]) < as_int(seq[*i - 1]))
+            .map(|i| i + 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn wraparound_tracks_field_size() {
+        // halo2curves has no small test field, so compare the 254-bit bn256 scalar field with
+        // the slightly larger pallas one: F_367 ~ 2.23e76 passes r ~ 2.19e76 but not q ~ 2.89e76
+        assert_eq!(wrap_index::<bn256::Fr>(), 367);
+        assert_eq!(wrap_index::<pasta::Fq>(), 368);
+
+        let one = Fr::from(1);
+        assert!(binet_check(366, compute(366, one, one)));
+        assert!(!binet_check(367, compute(367, one, one)));
+    }
+
+    #[test]
+    fn unwrapped_index_precedes_wrap() {
+        assert_eq!(max_unwrapped_index::<bn256::Fr>(), 366);
+        assert_eq!(max_unwrapped_index::<pasta::Fq>(), 367);
+        assert_eq!(
+            max_unwrapped_index::<bn256::Fr>() + 1,
+            wrap_index::<bn256::Fr>()
+        );
+    }
+}
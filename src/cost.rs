@@ -0,0 +1,178 @@
+use crate::circuit::{rows_required, FibCircuit};
+use crate::prover::{constraint_system, ProofHeader};
+use halo2_base::halo2_proofs::{
+    dev::CircuitCost,
+    halo2curves::bn256::{Fr, G1},
+    plonk::{Circuit, ConstraintSystem},
+};
+use serde::Serialize;
+
+/// Serializable summary of `CircuitCost` plus the shape numbers it keeps private
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CostReport {
+    pub k: u32,
+    pub rows: usize,
+    pub advice_columns: usize,
+    pub fixed_columns: usize,
+    pub instance_columns: usize,
+    pub degree: usize,
+    /// Bytes of a proof over a single instance column
+    pub proof_size: usize,
+    /// Bytes each extra advice column, lookup, or permutation column adds to the proof
+    pub marginal_proof_size: usize,
+}
+
+pub fn circuit_cost(k: u32, circuit: &FibCircuit) -> CircuitCost<G1, FibCircuit> {
+    CircuitCost::<G1, FibCircuit>::measure(k, circuit)
+}
+
+pub fn cost_report(k: u32, circuit: &FibCircuit) -> CostReport {
+    let cost = circuit_cost(k, circuit);
+
+    let mut cs = ConstraintSystem::<Fr>::default();
+    <FibCircuit as Circuit<Fr>>::configure(&mut cs);
+
+    CostReport {
+        k,
+        rows: rows_required(circuit.fib_size),
+        advice_columns: cs.num_advice_columns(),
+        fixed_columns: cs.num_fixed_columns(),
+        instance_columns: cs.num_instance_columns(),
+        degree: cs.degree(),
+        proof_size: cost.proof_size(1).into(),
+        marginal_proof_size: cost.marginal_proof_size().into(),
+    }
+}
+
+// Compressed commitments and scalars are both 32 bytes on bn256
+const ELEMENT_BYTES: usize = 32;
+
+/// Bytes of one SHPLONK proof covering `batch` `FibCircuit` statements at once, as
+/// `create_proof` writes when handed several circuits.
+///
+/// Each statement brings its own advice commitments and evaluations and its own permutation
+/// products; the vanishing argument, the fixed and permutation-column evaluations and the two
+/// opening commitments are written once for the whole batch. Selectors are counted as one
+/// fixed column each, which is what keygen leaves for `FibCircuit`.
+pub fn batch_proof_size(batch: usize) -> usize {
+    let cs = constraint_system();
+    let chunk = cs.degree().saturating_sub(2).max(1);
+    let permutation_columns = cs.permutation().get_columns().len();
+    let chunks = (permutation_columns + chunk - 1) / chunk;
+    let lookups = cs.lookups().len();
+
+    // Products are opened at x and x * omega, and all but the last at the last usable row too
+    let permutation_evals = (3 * chunks).saturating_sub(1);
+    let per_statement = cs.num_advice_columns()
+        + chunks
+        + 3 * lookups
+        + cs.advice_queries().len()
+        + permutation_evals
+        + 5 * lookups;
+
+    // The vanishing argument's random commitment and evaluation around the quotient pieces,
+    // then SHPLONK's two opening commitments
+    let quotient_pieces = cs.degree() - 1;
+    let fixed_evals = cs.fixed_queries().len() + cs.num_selectors();
+    let shared = 1 + quotient_pieces + 1 + fixed_evals + permutation_columns + 2;
+
+    ProofHeader::LEN + (shared + batch * per_statement) * ELEMENT_BYTES
+}
+
+/// Largest batch of at most `num_proofs` statements whose combined proof fits in
+/// `target_proof_bytes`, by `batch_proof_size`. Zero when not even one statement fits.
+pub fn optimal_batch_size(num_proofs: usize, target_proof_bytes: usize) -> usize {
+    let single = batch_proof_size(1);
+    if target_proof_bytes < single {
+        return 0;
+    }
+    let per_statement = batch_proof_size(2) - single;
+    let batch = 1 + (target_proof_bytes - single) / per_statement;
+    batch.min(num_proofs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prover::min_k_for;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::{dev::MockProver, plonk};
+
+    #[test]
+    fn report_matches_layout() {
+        let circuit = FibCircuit {
+            fib_size: 1000,
+            ..Default::default()
+        };
+        let k = min_k_for(circuit.fib_size);
+        let report = cost_report(k, &circuit);
+
+        assert_eq!(report.rows, 998);
+        assert_eq!(report.advice_columns, 3);
+        assert_eq!(report.instance_columns, 1);
+        assert!(report.proof_size > report.marginal_proof_size);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["rows"], 998);
+    }
+
+    #[test]
+    fn report_rows_fill_the_layout() {
+        // At k = 4 the prover leaves 16 minus the blinding rows; the report claims exactly
+        // that many for fib_size 12, so synthesis must fit there and fail one term later
+        let usable = (1 << 4) - (constraint_system().blinding_factors() + 1);
+        let fits = FibCircuit {
+            fib_size: 12,
+            ..Default::default()
+        };
+        assert_eq!(cost_report(4, &fits).rows, usable);
+
+        let instance = vec![vec![Fr::from(1), Fr::from(1)]];
+        assert_satisfied_verbose(&MockProver::run(4, &fits, instance.clone()).unwrap());
+
+        let over = FibCircuit {
+            fib_size: 13,
+            ..Default::default()
+        };
+        assert_eq!(cost_report(4, &over).rows, usable + 1);
+        assert!(matches!(
+            MockProver::run(4, &over, instance),
+            Err(plonk::Error::NotEnoughRowsAvailable { current_k: 4 })
+        ));
+    }
+
+    #[test]
+    fn batch_size_grows_with_target() {
+        use crate::prover::{gen_params, gen_pk, min_k_for, prove_fib};
+
+        // The model matches what the prover actually writes for a single statement
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+        assert_eq!(batch_proof_size(1), proof.len());
+
+        let targets = [1 << 10, 1 << 12, 1 << 14, 1 << 16];
+        let batches: Vec<_> = targets
+            .iter()
+            .map(|target| optimal_batch_size(1000, *target))
+            .collect();
+        assert!(
+            batches.windows(2).all(|pair| pair[0] < pair[1]),
+            "{batches:?}"
+        );
+
+        // Each is the largest that fits
+        for (target, batch) in targets.into_iter().zip(batches) {
+            assert!(batch_proof_size(batch) <= target);
+            assert!(batch_proof_size(batch + 1) > target);
+        }
+
+        assert_eq!(optimal_batch_size(1000, 100), 0);
+        assert_eq!(optimal_batch_size(3, 1 << 20), 3);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::mod_step::{ModStepChip, ModStepConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b]
+const SEED_ROWS: [usize; 2] = [0, 1];
+
+#[derive(Debug, Clone)]
+pub struct CyclicConfig {
+    pub sequence: ModStepConfig,
+    pub instance: Column<Instance>,
+}
+
+/// Proves the Fibonacci sequence mod m from public seeds closes into a loop of a given length.
+///
+/// Row i holds (x_i, x_{i+1}) mod m, stepped by `ModStepChip`. Row `len` is then
+/// copy-constrained back onto row 0, so the successor of the last term must equal the first
+/// seed (and the one after it the second). `len` need not be the shortest loop: any multiple
+/// of it closes as well.
+pub struct CyclicChip<F: FieldExt> {
+    config: CyclicConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CyclicChip<F> {
+    pub fn construct(config: CyclicConfig) -> CyclicChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_cyclic(
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> CyclicConfig {
+        let sequence = ModStepChip::configure_mod_step(cs);
+
+        // Seeds come in from the instance, and the last row wraps back onto them
+        cs.enable_equality(sequence.a);
+        cs.enable_equality(sequence.b);
+        cs.enable_equality(instance);
+
+        CyclicConfig { sequence, instance }
+    }
+
+    pub fn load_table(&self, layouter: impl Layouter<F>, modulus: u64) -> Result<(), Error> {
+        ModStepChip::construct(self.config.sequence.clone()).load_table(layouter, modulus)
+    }
+
+    /// Runs `steps` steps mod `modulus` from the instance seeds and returns the seed cells and
+    /// the pair they end on, (x_steps, x_{steps+1}) mod m
+    pub fn assign_steps(
+        &self,
+        mut layouter: impl Layouter<F>,
+        modulus: u64,
+        steps: usize,
+    ) -> Result<
+        (
+            (AssignedCell<F, F>, AssignedCell<F, F>),
+            (AssignedCell<F, F>, AssignedCell<F, F>),
+        ),
+        Error,
+    > {
+        let config = &self.config;
+        let sequence = &config.sequence;
+        let step = ModStepChip::construct(sequence.clone());
+        if modulus < 2 || steps == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "mod steps",
+            |mut region| {
+                let [row_a, row_b] = SEED_ROWS;
+                let seed_a = region.assign_advice_from_instance(
+                    || "a",
+                    config.instance,
+                    row_a,
+                    sequence.a,
+                    0,
+                )?;
+                let seed_b = region.assign_advice_from_instance(
+                    || "b",
+                    config.instance,
+                    row_b,
+                    sequence.b,
+                    0,
+                )?;
+
+                let (mut a, mut b) = (seed_a.value().copied(), seed_b.value().copied());
+                let mut last = (seed_a.clone(), seed_b.clone());
+
+                for row in 0..steps {
+                    (a, b) = step.assign_step(&mut region, row, modulus, a, b)?;
+                    last = (
+                        region.assign_advice(|| "a", sequence.a, row + 1, || a)?,
+                        region.assign_advice(|| "b", sequence.b, row + 1, || b)?,
+                    );
+                }
+                sequence.range.enable(&mut region, steps)?;
+
+                Ok(((seed_a, seed_b), last))
+            },
+        )
+    }
+
+    /// Runs `len` steps mod `modulus` from the instance seeds and wraps the result back onto
+    /// them
+    pub fn assign_cycle(
+        &self,
+        mut layouter: impl Layouter<F>,
+        modulus: u64,
+        len: usize,
+    ) -> Result<(), Error> {
+        let (seeds, last) = self.assign_steps(layouter.namespace(|| "steps"), modulus, len)?;
+
+        // The wrap-around: the pair after the last step is the pair we started from
+        layouter.assign_region(
+            || "wrap",
+            |mut region| {
+                region.constrain_equal(last.0.cell(), seeds.0.cell())?;
+                region.constrain_equal(last.1.cell(), seeds.1.cell())
+            },
+        )
+    }
+}
+
+/// Instance: [seed_a, seed_b], both below the modulus. The modulus and loop length fix the
+/// circuit shape, so keys are per (modulus, len).
+#[derive(Clone, Debug, Default)]
+pub struct CyclicCircuit {
+    pub modulus: u64,
+    pub len: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for CyclicCircuit {
+    type Config = CyclicConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let instance = cs.instance_column();
+        CyclicChip::configure_cyclic(instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = CyclicChip::construct(config);
+
+        chip.load_table(layouter.namespace(|| "residue table"), self.modulus)?;
+        chip.assign_cycle(layouter.namespace(|| "cycle"), self.modulus, self.len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pisano::pisano_period;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    fn run(len: usize, seeds: (u64, u64)) -> MockProver<Fr> {
+        let circuit = CyclicCircuit { modulus: 10, len };
+        let instance = vec![Fr::from(seeds.0), Fr::from(seeds.1)];
+        MockProver::run(7, &circuit, vec![instance]).unwrap()
+    }
+
+    #[test]
+    fn loop_closes_at_pisano_period() {
+        let period = pisano_period(10);
+
+        // Every pair mod 10 lies on a loop whose length divides π(10) = 60
+        assert_satisfied_verbose(&run(period, (0, 1)));
+        assert_satisfied_verbose(&run(period, (2, 7)));
+
+        assert!(run(period - 1, (0, 1)).verify().is_err());
+        assert!(run(period + 1, (2, 7)).verify().is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::private_seeds::{poseidon_hash, PoseidonCommit, SeedCommit};
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*};
+use halo2_gadgets::poseidon::Pow5Config;
+
+// Instance layout: [seed_a, seed_b, digest]
+const DIGEST_ROW: usize = 2;
+
+/// Off-circuit digest of a whole trace: H(H(H(t_1, t_2), t_3), ...) with the two-to-one
+/// `poseidon_hash`. `None` for fewer than two terms.
+pub fn trace_digest<F: FieldExt>(terms: &[F]) -> Option<F> {
+    let (first, rest) = terms.split_first()?;
+    let (second, rest) = rest.split_first()?;
+
+    let seed = poseidon_hash(*first, *second);
+    Some(
+        rest.iter()
+            .fold(seed, |acc, term| poseidon_hash(acc, *term)),
+    )
+}
+
+/// Folds `terms` into one running Poseidon digest, matching `trace_digest`
+pub fn digest_cells<F: FieldExt>(
+    config: &Pow5Config<F, 3, 2>,
+    mut layouter: impl Layouter<F>,
+    terms: &[AssignedCell<F, F>],
+) -> Result<AssignedCell<F, F>, Error> {
+    let (first, rest) = terms.split_first().ok_or(Error::Synthesis)?;
+    let (second, rest) = rest.split_first().ok_or(Error::Synthesis)?;
+
+    let mut digest =
+        PoseidonCommit::hash_cells(config.clone(), layouter.namespace(|| "seed"), first, second)?;
+    for term in rest {
+        digest = PoseidonCommit::hash_cells(
+            config.clone(),
+            layouter.namespace(|| "fold"),
+            &digest,
+            term,
+        )?;
+    }
+    Ok(digest)
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceDigestConfig<F: FieldExt> {
+    pub fib: FibConfig,
+    pub poseidon: Pow5Config<F, 3, 2>,
+}
+
+impl<F: FieldExt> TraceDigestConfig<F> {
+    pub fn configure(cs: &mut ConstraintSystem<F>) -> Self {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+        let fib = FibChip::configure(advice, instance, cs);
+        let poseidon = PoseidonCommit::configure(cs, advice);
+
+        Self { fib, poseidon }
+    }
+}
+
+/// Fibonacci that also exposes `trace_digest` of every term F_1..=F_{fib_size}, so changing
+/// any intermediate term, not just the output, changes the public value
+#[derive(Clone, Debug, Default)]
+pub struct TraceDigestCircuit {
+    pub fib_size: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for TraceDigestCircuit {
+    type Config = TraceDigestConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        TraceDigestConfig::configure(cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        let chip = FibChip::construct(config.fib);
+        let (a, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+        let mut terms = vec![a, b.clone(), c.clone()];
+        for _ in 3..self.fib_size {
+            let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            terms.push(new_c.clone());
+            b = c;
+            c = new_c;
+        }
+
+        let digest = digest_cells(&config.poseidon, layouter.namespace(|| "digest"), &terms)?;
+        chip.expose_public(layouter.namespace(|| "digest"), &digest, DIGEST_ROW)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::precompute_sequence;
+    use crate::testing::{assert_satisfied_verbose, StubbedTraceCircuit, TraceCommitment};
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    impl TraceCommitment for TraceDigestCircuit {
+        const ROW: usize = DIGEST_ROW;
+
+        fn commit(
+            config: &TraceDigestConfig<Fr>,
+            layouter: impl Layouter<Fr>,
+            terms: &[AssignedCell<Fr, Fr>],
+        ) -> Result<AssignedCell<Fr, Fr>, Error> {
+            digest_cells(&config.poseidon, layouter, terms)
+        }
+    }
+
+    #[test]
+    fn digest_covers_every_term() {
+        let (a, b) = (Fr::from(1), Fr::from(1));
+        let terms = precompute_sequence(10, a, b);
+        let digest = trace_digest(&terms).unwrap();
+
+        let circuit = TraceDigestCircuit { fib_size: 10 };
+        let prover = MockProver::run(10, &circuit, vec![vec![a, b, digest]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        // Same seeds and output, one intermediate term swapped out
+        let mut tampered = terms.clone();
+        tampered[5] += Fr::from(1);
+        let tampered_digest = trace_digest(&tampered).unwrap();
+        assert_ne!(tampered_digest, digest);
+
+        let stubbed = StubbedTraceCircuit::<TraceDigestCircuit>::new(tampered);
+        let prover = MockProver::run(10, &stubbed, vec![vec![a, b, digest]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        let prover = MockProver::run(10, &stubbed, vec![vec![a, b, tampered_digest]]).unwrap();
+        assert_satisfied_verbose(&prover);
+    }
+}
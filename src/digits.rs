@@ -0,0 +1,273 @@
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+/// Most digits a decomposition may use: 10^76 is still below the bn256 scalar modulus, so a
+/// 76-digit recomposition cannot wrap around and alias another value
+pub const MAX_DIGITS: usize = 76;
+
+#[derive(Debug, Clone)]
+pub struct DigitsConfig {
+    pub digit: Column<Advice>,
+    pub acc: Column<Advice>,
+    pub table: TableColumn,
+    pub lookup: Selector,
+    pub first: Selector,
+    pub step: Selector,
+}
+
+/// Splits a cell into base-10 digits, most significant first.
+///
+/// Each digit is looked up in a 0..=9 table and `acc` rebuilds the value Horner-style down
+/// the region (`acc = 10 * acc_prev + digit`), so the last `acc` is copy-constrained to the
+/// input. The digit count is part of the circuit shape; shorter values get leading zeros.
+pub struct DigitsChip<F: FieldExt> {
+    config: DigitsConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DigitsChip<F> {
+    pub fn construct(config: DigitsConfig) -> DigitsChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_digits(cs: &mut ConstraintSystem<F>) -> DigitsConfig {
+        let digit = cs.advice_column();
+        let acc = cs.advice_column();
+        let table = cs.lookup_table_column();
+        let lookup = cs.complex_selector();
+        let first = cs.selector();
+        let step = cs.selector();
+
+        // Digits go out to the instance, the recomposed value is tied back to its source
+        cs.enable_equality(digit);
+        cs.enable_equality(acc);
+
+        // A disabled row looks up 0, which is always in the table
+        cs.lookup("decimal digit", |cells| {
+            let s = cells.query_selector(lookup);
+            let digit = cells.query_advice(digit, Rotation::cur());
+
+            vec![(s * digit, table)]
+        });
+
+        cs.create_gate("digits first", |cells| {
+            let s = cells.query_selector(first);
+            let digit = cells.query_advice(digit, Rotation::cur());
+            let acc = cells.query_advice(acc, Rotation::cur());
+
+            vec![s * (acc - digit)]
+        });
+
+        cs.create_gate("digits step", |cells| {
+            let s = cells.query_selector(step);
+            let digit = cells.query_advice(digit, Rotation::cur());
+            let acc = cells.query_advice(acc, Rotation::cur());
+            let acc_prev = cells.query_advice(acc, Rotation::prev());
+
+            vec![s * (acc - acc_prev * Expression::Constant(F::from(10)) - digit)]
+        });
+
+        DigitsConfig {
+            digit,
+            acc,
+            table,
+            lookup,
+            first,
+            step,
+        }
+    }
+
+    pub fn load_table(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "decimal digits",
+            |mut table| {
+                for d in 0..10 {
+                    table.assign_cell(
+                        || "digit",
+                        self.config.table,
+                        d,
+                        || Value::known(F::from(d as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Returns `num_digits` digit cells of `value`, most significant first. A value that needs
+    /// more digits leaves the recomposition unsatisfied.
+    pub fn assign_digits(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: &AssignedCell<F, F>,
+        num_digits: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        if num_digits == 0 || num_digits > MAX_DIGITS {
+            return Err(Error::Synthesis);
+        }
+        let config = &self.config;
+        let digits = value.value().map(|v| decimal_digits(*v, num_digits));
+
+        layouter.assign_region(
+            || "digits",
+            |mut region| {
+                let mut acc = Value::known(F::zero());
+                let mut cells = Vec::with_capacity(num_digits);
+                let mut acc_cell = None;
+
+                for row in 0..num_digits {
+                    config.lookup.enable(&mut region, row)?;
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else {
+                        config.step.enable(&mut region, row)?;
+                    }
+
+                    let digit = digits.as_ref().map(|d| F::from(d[row]));
+                    cells.push(region.assign_advice(|| "digit", config.digit, row, || digit)?);
+
+                    acc = acc * Value::known(F::from(10)) + digit;
+                    acc_cell = Some(region.assign_advice(|| "acc", config.acc, row, || acc)?);
+                }
+
+                // Pins the recomposed number to the value being displayed
+                let acc_cell = acc_cell.ok_or(Error::Synthesis)?;
+                region.constrain_equal(acc_cell.cell(), value.cell())?;
+
+                Ok(cells)
+            },
+        )
+    }
+}
+
+/// The low `num_digits` decimal digits of `value`'s canonical integer, most significant first
+fn decimal_digits<F: FieldExt>(value: F, num_digits: usize) -> Vec<u64> {
+    // Little-endian base-2^64 limbs, divided down by 10 one digit at a time
+    let mut limbs: Vec<u64> = value
+        .to_repr()
+        .as_ref()
+        .chunks(8)
+        .map(|chunk| {
+            let mut bytes = [0u8; 8];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            u64::from_le_bytes(bytes)
+        })
+        .collect();
+
+    let mut digits = vec![0; num_digits];
+    for slot in digits.iter_mut().rev() {
+        let mut rem = 0u128;
+        for limb in limbs.iter_mut().rev() {
+            let cur = (rem << 64) | *limb as u128;
+            *limb = (cur / 10) as u64;
+            rem = cur % 10;
+        }
+        *slot = rem as u64;
+    }
+    digits
+}
+
+#[derive(Debug, Clone)]
+pub struct DigitsCircuitConfig {
+    pub fib: FibConfig,
+    pub digits: DigitsConfig,
+}
+
+/// `FibCircuit` with F_{fib_size} exposed as `num_digits` decimal digits at instance rows
+/// 2.., after the seeds
+#[derive(Clone, Debug, Default)]
+pub struct DigitsCircuit {
+    pub fib_size: usize,
+    pub num_digits: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for DigitsCircuit {
+    type Config = DigitsCircuitConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        DigitsCircuitConfig {
+            fib: FibChip::configure(advice, instance, cs),
+            digits: DigitsChip::configure_digits(cs),
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        let chip = FibChip::construct(config.fib);
+        let (_, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+        for _ in 3..self.fib_size {
+            let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            b = c;
+            c = new_c;
+        }
+
+        let digits_chip = DigitsChip::construct(config.digits);
+        digits_chip.load_table(layouter.namespace(|| "digit table"))?;
+        let digits =
+            digits_chip.assign_digits(layouter.namespace(|| "digits"), &c, self.num_digits)?;
+
+        for (i, digit) in digits.iter().enumerate() {
+            chip.expose_public(layouter.namespace(|| "digit"), digit, 2 + i)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn fib_10_digits() {
+        assert_eq!(
+            decimal_digits(Fr::from(1234567u64), 9),
+            [0, 0, 1, 2, 3, 4, 5, 6, 7]
+        );
+
+        // F_10 = 55
+        let circuit = DigitsCircuit {
+            fib_size: 10,
+            num_digits: 2,
+        };
+        let one = Fr::from(1);
+        let instance = vec![one, one, Fr::from(5), Fr::from(5)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let instance = vec![one, one, Fr::from(4), Fr::from(15)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // 55 does not fit in one digit
+        let circuit = DigitsCircuit {
+            fib_size: 10,
+            num_digits: 1,
+        };
+        let instance = vec![one, one, Fr::from(5)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{FibChip, FibConfig, InstanceLayout};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [a, b, a', b', first diverging index]
+const SECOND_SEED_ROWS: [usize; 2] = [2, 3];
+const INDEX_ROW: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct DivergenceConfig {
+    pub fib: FibConfig,
+    /// Term i of each sequence on row i
+    pub terms: [Column<Advice>; 2],
+    /// Inverse of the difference where it is nonzero
+    pub inv: Column<Advice>,
+    /// 1 where the terms differ
+    pub differs: Column<Advice>,
+    /// 1 from the first differing row on
+    pub seen: Column<Advice>,
+    /// Rows so far before the first difference
+    pub count: Column<Advice>,
+    pub row: Selector,
+    pub first: Selector,
+    pub step: Selector,
+}
+
+/// Compares two sequences term by term and yields the 0-based index of the first term where
+/// they differ, or their length if they agree throughout.
+///
+/// Each row gets an is-nonzero flag for the difference (d * inv = flag, d * (1 - flag) = 0),
+/// `seen` ORs the flags down the region, and `count` adds one for every row still unseen, so
+/// its last value is the index of the first difference.
+pub struct DivergenceChip<F: FieldExt> {
+    config: DivergenceConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DivergenceChip<F> {
+    pub fn construct(config: DivergenceConfig) -> DivergenceChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_divergence(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> DivergenceConfig {
+        let fib = FibChip::configure(advice, instance, cs);
+        let terms = [cs.advice_column(), cs.advice_column()];
+        let inv = cs.advice_column();
+        let differs = cs.advice_column();
+        let seen = cs.advice_column();
+        let count = cs.advice_column();
+        let row = cs.selector();
+        let first = cs.selector();
+        let step = cs.selector();
+
+        for column in terms {
+            cs.enable_equality(column);
+        }
+        cs.enable_equality(count);
+
+        cs.create_gate("divergence row", |cells| {
+            let s = cells.query_selector(row);
+            let [x, y] = terms.map(|column| cells.query_advice(column, Rotation::cur()));
+            let inv = cells.query_advice(inv, Rotation::cur());
+            let differs = cells.query_advice(differs, Rotation::cur());
+            let one = Expression::Constant(F::one());
+            let diff = x - y;
+
+            vec![
+                s.clone() * (diff.clone() * inv - differs.clone()),
+                s * diff * (one - differs),
+            ]
+        });
+
+        cs.create_gate("divergence first", |cells| {
+            let s = cells.query_selector(first);
+            let differs = cells.query_advice(differs, Rotation::cur());
+            let seen = cells.query_advice(seen, Rotation::cur());
+            let count = cells.query_advice(count, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * (seen.clone() - differs),
+                s * (count - (one - seen)),
+            ]
+        });
+
+        cs.create_gate("divergence step", |cells| {
+            let s = cells.query_selector(step);
+            let differs = cells.query_advice(differs, Rotation::cur());
+            let seen = cells.query_advice(seen, Rotation::cur());
+            let seen_prev = cells.query_advice(seen, Rotation::prev());
+            let count = cells.query_advice(count, Rotation::cur());
+            let count_prev = cells.query_advice(count, Rotation::prev());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone()
+                    * (seen.clone() - (seen_prev.clone() + differs.clone() - seen_prev * differs)),
+                s * (count - count_prev - (one - seen)),
+            ]
+        });
+
+        DivergenceConfig {
+            fib,
+            terms,
+            inv,
+            differs,
+            seen,
+            count,
+            row,
+            first,
+            step,
+        }
+    }
+
+    /// Copies both sequences side by side and returns the cell holding the first index where
+    /// they differ
+    pub fn assign_divergence(
+        &self,
+        mut layouter: impl Layouter<F>,
+        xs: &[AssignedCell<F, F>],
+        ys: &[AssignedCell<F, F>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = &self.config;
+        if xs.is_empty() || xs.len() != ys.len() {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "divergence",
+            |mut region| {
+                let mut seen = Value::known(F::zero());
+                let mut count = Value::known(F::zero());
+                let mut out = None;
+
+                for (row, (x, y)) in xs.iter().zip(ys).enumerate() {
+                    config.row.enable(&mut region, row)?;
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else {
+                        config.step.enable(&mut region, row)?;
+                    }
+
+                    x.copy_advice(|| "x", &mut region, config.terms[0], row)?;
+                    y.copy_advice(|| "y", &mut region, config.terms[1], row)?;
+
+                    let diff = x.value().copied() - y.value();
+                    let inv = diff.map(|d| d.invert().unwrap_or(F::zero()));
+                    let differs = diff.map(|d| if d == F::zero() { F::zero() } else { F::one() });
+                    region.assign_advice(|| "inv", config.inv, row, || inv)?;
+                    region.assign_advice(|| "differs", config.differs, row, || differs)?;
+
+                    seen = seen + differs - seen * differs;
+                    count = count + Value::known(F::one()) - seen;
+                    region.assign_advice(|| "seen", config.seen, row, || seen)?;
+                    out = Some(region.assign_advice(|| "count", config.count, row, || count)?);
+                }
+
+                out.ok_or(Error::Synthesis)
+            },
+        )
+    }
+}
+
+/// Runs `len` terms from each of two seed pairs and exposes where they first differ
+#[derive(Clone, Debug, Default)]
+pub struct DivergenceCircuit {
+    pub len: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for DivergenceCircuit {
+    type Config = DivergenceConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        DivergenceChip::configure_divergence(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.len < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        let layouts = [
+            InstanceLayout::SeedsFirst,
+            InstanceLayout::Custom(SECOND_SEED_ROWS.to_vec()),
+        ];
+        let mut sequences = vec![];
+        for layout in layouts {
+            let chip = FibChip::construct_with_layout(config.fib.clone(), layout);
+            let (a, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+            let mut terms = vec![a, b.clone(), c.clone()];
+            for _ in 3..self.len {
+                let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+                terms.push(new_c.clone());
+                b = c;
+                c = new_c;
+            }
+            sequences.push(terms);
+        }
+
+        let chip = DivergenceChip::construct(config.clone());
+        let index = chip.assign_divergence(
+            layouter.namespace(|| "compare"),
+            &sequences[0],
+            &sequences[1],
+        )?;
+        FibChip::construct(config.fib).expose_public(
+            layouter.namespace(|| "index"),
+            &index,
+            INDEX_ROW,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    fn run(seeds: [u64; 4], index: u64) -> MockProver<Fr> {
+        let mut instance: Vec<_> = seeds.into_iter().map(Fr::from).collect();
+        instance.push(Fr::from(index));
+        MockProver::run(5, &DivergenceCircuit { len: 10 }, vec![instance]).unwrap()
+    }
+
+    #[test]
+    fn first_difference() {
+        // Identical sequences never diverge, so the index is the length
+        assert_satisfied_verbose(&run([1, 1, 1, 1], 10));
+        assert!(run([1, 1, 1, 1], 9).verify().is_err());
+
+        // F_1 already differs
+        assert_satisfied_verbose(&run([1, 1, 2, 1], 0));
+        // 1, 1, 2, 3 against 1, 2, 3, 5
+        assert_satisfied_verbose(&run([1, 1, 1, 2], 1));
+        assert!(run([1, 1, 1, 2], 2).verify().is_err());
+    }
+}
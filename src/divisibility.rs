@@ -0,0 +1,156 @@
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b, F_m, F_{mn}]
+const F_M_ROW: usize = 2;
+const F_MN_ROW: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct DivisibilityConfig {
+    pub fib: FibConfig,
+    /// F_m, the private quotient q and F_{mn} in one row
+    pub terms: [Column<Advice>; 3],
+    pub selector: Selector,
+}
+
+/// Checks F_{mn} = F_m * q for a private quotient q, with both terms taken from one chain.
+///
+/// In the field every nonzero F_m divides everything, so the relation only carries the
+/// integer meaning F_m | F_{mn} while F_{mn} is below the modulus and q is the integer
+/// quotient. Nothing here range-checks q, so that is a statement about honest witnesses.
+pub struct DivisibilityChip<F: FieldExt> {
+    config: DivisibilityConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DivisibilityChip<F> {
+    pub fn construct(config: DivisibilityConfig) -> DivisibilityChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_divisibility(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> DivisibilityConfig {
+        let fib = FibChip::configure(advice, instance, cs);
+        let selector = cs.selector();
+
+        // Reuses the chain columns, which already take copies
+        cs.create_gate("divisibility", |cells| {
+            let s = cells.query_selector(selector);
+            let [f_m, q, f_mn] = advice.map(|column| cells.query_advice(column, Rotation::cur()));
+
+            vec![s * (f_m * q - f_mn)]
+        });
+
+        DivisibilityConfig {
+            fib,
+            terms: advice,
+            selector,
+        }
+    }
+
+    /// Copies F_m and F_{mn} into the quotient row next to q = F_{mn} / F_m
+    pub fn assign_quotient(
+        &self,
+        mut layouter: impl Layouter<F>,
+        f_m: &AssignedCell<F, F>,
+        f_mn: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        let [col_m, col_q, col_mn] = self.config.terms;
+
+        layouter.assign_region(
+            || "divisibility",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                f_m.copy_advice(|| "F_m", &mut region, col_m, 0)?;
+                f_mn.copy_advice(|| "F_{mn}", &mut region, col_mn, 0)?;
+
+                let q =
+                    f_mn.value().copied() * f_m.value().map(|v| v.invert().unwrap_or(F::zero()));
+                region.assign_advice(|| "q", col_q, 0, || q)?;
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Proves F_m | F_{mn} for the chain from the instance seeds, exposing both terms
+#[derive(Clone, Debug, Default)]
+pub struct DivisibilityCircuit {
+    pub m: usize,
+    pub n: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for DivisibilityCircuit {
+    type Config = DivisibilityConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        DivisibilityChip::configure_divisibility(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let len = self.m * self.n;
+        if self.m == 0 || len < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        let fib = FibChip::construct(config.fib.clone());
+        let (a, mut b, mut c) = fib.assign_first_row(layouter.namespace(|| "first row"))?;
+        let mut terms = vec![a, b.clone(), c.clone()];
+        for _ in 3..len {
+            let new_c = fib.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            terms.push(new_c.clone());
+            b = c;
+            c = new_c;
+        }
+
+        let (f_m, f_mn) = (&terms[self.m - 1], &terms[len - 1]);
+        let chip = DivisibilityChip::construct(config);
+        chip.assign_quotient(layouter.namespace(|| "quotient"), f_m, f_mn)?;
+
+        fib.expose_public(layouter.namespace(|| "F_m"), f_m, F_M_ROW)?;
+        fib.expose_public(layouter.namespace(|| "F_{mn}"), f_mn, F_MN_ROW)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn f_4_divides_f_12() {
+        // F_12 = 144 = 3 * 48 = F_4 * 48
+        let circuit = DivisibilityCircuit { m: 4, n: 3 };
+        let one = Fr::from(1);
+
+        let instance = vec![one, one, Fr::from(3), Fr::from(144)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let instance = vec![one, one, Fr::from(3), Fr::from(145)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
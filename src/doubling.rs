@@ -0,0 +1,242 @@
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [n, F_n]
+const N_ROW: usize = 0;
+const VALUE_ROW: usize = 1;
+
+#[derive(Debug, Clone)]
+pub struct DoublingConfig {
+    /// F_k and F_{k+1} for k the bits of n read so far
+    pub f: Column<Advice>,
+    pub g: Column<Advice>,
+    pub bit: Column<Advice>,
+    /// k itself, rebuilt from the bits
+    pub acc: Column<Advice>,
+    pub step: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// Computes F_n in one row per bit of n with the fast-doubling identities
+/// F_{2k} = F_k (2 F_{k+1} - F_k) and F_{2k+1} = F_k^2 + F_{k+1}^2.
+///
+/// Row 0 holds (F_0, F_1) = (0, 1). Each later row reads the next bit of n, most significant
+/// first, and takes (F_k, F_{k+1}) to (F_{2k}, F_{2k+1}) on a 0 and (F_{2k+1}, F_{2k+2}) on a
+/// 1, while `acc` doubles and adds the bit. Leading zero bits leave (0, 1) in place, so the
+/// number of bits fixes the shape of the circuit and any n below 2^bits fits it. Unlike
+/// `FibChip` the seeds are fixed to the standard (1, 1).
+pub struct DoublingChip<F: FieldExt> {
+    config: DoublingConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DoublingChip<F> {
+    pub fn construct(config: DoublingConfig) -> DoublingChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_doubling(
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> DoublingConfig {
+        let f = cs.advice_column();
+        let g = cs.advice_column();
+        let bit = cs.advice_column();
+        let acc = cs.advice_column();
+        let constant = cs.fixed_column();
+        let step = cs.selector();
+
+        cs.enable_equality(f);
+        cs.enable_equality(g);
+        cs.enable_equality(acc);
+        cs.enable_equality(instance);
+        cs.enable_constant(constant);
+
+        cs.create_gate("doubling step", |cells| {
+            let s = cells.query_selector(step);
+            let one = Expression::Constant(F::one());
+            let two = Expression::Constant(F::from(2));
+            let f_prev = cells.query_advice(f, Rotation::prev());
+            let g_prev = cells.query_advice(g, Rotation::prev());
+            let acc_prev = cells.query_advice(acc, Rotation::prev());
+            let f = cells.query_advice(f, Rotation::cur());
+            let g = cells.query_advice(g, Rotation::cur());
+            let bit = cells.query_advice(bit, Rotation::cur());
+            let acc = cells.query_advice(acc, Rotation::cur());
+
+            let even = f_prev.clone() * (two.clone() * g_prev.clone() - f_prev.clone());
+            let odd = f_prev.clone() * f_prev + g_prev.clone() * g_prev;
+
+            vec![
+                s.clone() * bit.clone() * (one - bit.clone()),
+                s.clone() * (f - even.clone() - bit.clone() * (odd.clone() - even.clone())),
+                s.clone() * (g - odd - bit.clone() * even),
+                s * (acc - two * acc_prev - bit),
+            ]
+        });
+
+        DoublingConfig {
+            f,
+            g,
+            bit,
+            acc,
+            step,
+            instance,
+        }
+    }
+
+    /// Assigns one row per bit of `n` below `num_bits` and returns the cells holding n and F_n
+    pub fn assign_doubling(
+        &self,
+        mut layouter: impl Layouter<F>,
+        n: Value<u64>,
+        num_bits: usize,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), Error> {
+        let config = &self.config;
+        if num_bits == 0 || num_bits > 64 {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "doubling",
+            |mut region| {
+                let mut f = region
+                    .assign_advice_from_constant(|| "F_0", config.f, 0, F::zero())?
+                    .value()
+                    .copied();
+                let mut g = region
+                    .assign_advice_from_constant(|| "F_1", config.g, 0, F::one())?
+                    .value()
+                    .copied();
+                let mut acc =
+                    region.assign_advice_from_constant(|| "k", config.acc, 0, F::zero())?;
+                let mut f_cell = None;
+
+                for row in 1..=num_bits {
+                    config.step.enable(&mut region, row)?;
+
+                    let bit = n.map(|n| (n >> (num_bits - row)) & 1 == 1);
+                    let bit_value = bit.map(|bit| if bit { F::one() } else { F::zero() });
+                    region.assign_advice(|| "bit", config.bit, row, || bit_value)?;
+
+                    let pair = bit.zip(f).zip(g).map(|((bit, f), g)| {
+                        let even = f * (g.double() - f);
+                        let odd = f.square() + g.square();
+                        if bit {
+                            (odd, even + odd)
+                        } else {
+                            (even, odd)
+                        }
+                    });
+                    f = pair.map(|(f, _)| f);
+                    g = pair.map(|(_, g)| g);
+                    f_cell = Some(region.assign_advice(|| "f", config.f, row, || f)?);
+                    region.assign_advice(|| "g", config.g, row, || g)?;
+
+                    let k = acc.value().map(|acc| acc.double()) + bit_value;
+                    acc = region.assign_advice(|| "k", config.acc, row, || k)?;
+                }
+
+                Ok((acc, f_cell.ok_or(Error::Synthesis)?))
+            },
+        )
+    }
+
+    pub fn expose_result(
+        &self,
+        mut layouter: impl Layouter<F>,
+        n: &AssignedCell<F, F>,
+        value: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(n.cell(), self.config.instance, N_ROW)?;
+        layouter.constrain_instance(value.cell(), self.config.instance, VALUE_ROW)
+    }
+}
+
+/// Instance: [n, F_n]. `num_bits` fixes the circuit shape; any n below 2^num_bits fits it.
+#[derive(Clone, Debug, Default)]
+pub struct DoublingCircuit {
+    pub n: Value<u64>,
+    pub num_bits: usize,
+}
+
+impl DoublingCircuit {
+    /// A circuit just wide enough for `n`
+    pub fn new(n: u64) -> Self {
+        Self {
+            n: Value::known(n),
+            num_bits: (u64::BITS - n.leading_zeros()).max(1) as usize,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for DoublingCircuit {
+    type Config = DoublingConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            n: Value::unknown(),
+            num_bits: self.num_bits,
+        }
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let instance = cs.instance_column();
+        DoublingChip::configure_doubling(instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = DoublingChip::construct(config);
+
+        let (n, value) =
+            chip.assign_doubling(layouter.namespace(|| "doubling"), self.n, self.num_bits)?;
+        chip.expose_result(layouter.namespace(|| "result"), &n, &value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::{compute, FibCircuit};
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn matches_linear_chip() {
+        let one = Fr::from(1);
+
+        // Small enough to lay out linearly: both circuits accept the same F_100
+        let f_100 = compute(100, one, one);
+        let linear = FibCircuit {
+            fib_size: 100,
+            expose_output: true,
+            ..Default::default()
+        };
+        assert_satisfied_verbose(
+            &MockProver::run(8, &linear, vec![vec![one, one, f_100]]).unwrap(),
+        );
+        let doubling = DoublingCircuit::new(100);
+        let prover = MockProver::run(5, &doubling, vec![vec![Fr::from(100), f_100]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        // F_1000000 mod p in 21 rows against a million for the linear chip
+        let n = 1_000_000;
+        let doubling = DoublingCircuit::new(n);
+        assert_eq!(doubling.num_bits, 20);
+        let f_n = compute(n as usize, one, one);
+        let prover = MockProver::run(5, &doubling, vec![vec![Fr::from(n), f_n]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let prover = MockProver::run(5, &doubling, vec![vec![Fr::from(n), f_n + one]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
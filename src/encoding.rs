@@ -0,0 +1,123 @@
+use crate::error::Error;
+use halo2_base::halo2_proofs::halo2curves::{bn256::Fr, group::ff::PrimeField};
+
+const FIELD_BYTES: usize = 32;
+
+/// Byte order of each encoded field element and length prefix.
+///
+/// `Little` is exactly `Fr::to_repr`; `Big` is the same bytes reversed, which is what most
+/// non-Rust big-integer libraries expect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// Encodes instance columns as `[num_columns: u32][len: u32, elements...]...`
+pub fn encode_instance(instances: &[Vec<Fr>], endianness: Endianness) -> Vec<u8> {
+    let len = 4 + instances
+        .iter()
+        .map(|col| 4 + col.len() * FIELD_BYTES)
+        .sum::<usize>();
+    let mut bytes = Vec::with_capacity(len);
+
+    write_u32(&mut bytes, instances.len() as u32, endianness);
+    for column in instances {
+        write_u32(&mut bytes, column.len() as u32, endianness);
+        for value in column {
+            let mut repr = value.to_repr();
+            if endianness == Endianness::Big {
+                repr.as_mut().reverse();
+            }
+            bytes.extend_from_slice(repr.as_ref());
+        }
+    }
+    bytes
+}
+
+pub fn decode_instance(bytes: &[u8], endianness: Endianness) -> Result<Vec<Vec<Fr>>, Error> {
+    let mut reader = bytes;
+
+    let num_columns = read_u32(&mut reader, endianness)?;
+    let mut instances = Vec::new();
+    for _ in 0..num_columns {
+        let len = read_u32(&mut reader, endianness)? as usize;
+        let mut column = Vec::with_capacity(len.min(reader.len() / FIELD_BYTES));
+        for _ in 0..len {
+            let mut repr = <Fr as PrimeField>::Repr::default();
+            repr.as_mut()
+                .copy_from_slice(take(&mut reader, FIELD_BYTES)?);
+            if endianness == Endianness::Big {
+                repr.as_mut().reverse();
+            }
+            let value = Option::from(Fr::from_repr(repr))
+                .ok_or_else(|| Error::InvalidEncoding("non-canonical field element".into()))?;
+            column.push(value);
+        }
+        instances.push(column);
+    }
+
+    if !reader.is_empty() {
+        return Err(Error::InvalidEncoding(format!(
+            "{} trailing bytes",
+            reader.len()
+        )));
+    }
+    Ok(instances)
+}
+
+fn write_u32(bytes: &mut Vec<u8>, value: u32, endianness: Endianness) {
+    match endianness {
+        Endianness::Little => bytes.extend_from_slice(&value.to_le_bytes()),
+        Endianness::Big => bytes.extend_from_slice(&value.to_be_bytes()),
+    }
+}
+
+fn read_u32(reader: &mut &[u8], endianness: Endianness) -> Result<u32, Error> {
+    let bytes = take_array(reader)?;
+    Ok(match endianness {
+        Endianness::Little => u32::from_le_bytes(bytes),
+        Endianness::Big => u32::from_be_bytes(bytes),
+    })
+}
+
+/// Splits the next `len` bytes off the front of `reader`
+pub(crate) fn take<'a>(reader: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    if reader.len() < len {
+        return Err(Error::InvalidEncoding("unexpected end of input".into()));
+    }
+    let (head, tail) = reader.split_at(len);
+    *reader = tail;
+    Ok(head)
+}
+
+pub(crate) fn take_array<const N: usize>(reader: &mut &[u8]) -> Result<[u8; N], Error> {
+    Ok(take(reader, N)?.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use halo2_base::halo2_proofs::halo2curves::{group::ff::Field, FieldExt};
+
+    #[test]
+    fn instance_round_trip() {
+        // Values spanning every 64-bit limb, including p - 1
+        let instances = vec![
+            vec![Fr::from(1), Fr::from_u128(u128::MAX), -Fr::one()],
+            vec![],
+            vec![Fr::from(55)],
+        ];
+
+        for endianness in [Endianness::Little, Endianness::Big] {
+            let bytes = encode_instance(&instances, endianness);
+            assert_eq!(decode_instance(&bytes, endianness).unwrap(), instances);
+            assert!(decode_instance(&bytes[..bytes.len() - 1], endianness).is_err());
+        }
+
+        let little = encode_instance(&instances, Endianness::Little);
+        let big = encode_instance(&instances, Endianness::Big);
+        assert_ne!(little, big);
+        assert_eq!(&little[8..40], Fr::from(1).to_repr().as_ref());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::prover::MultiOpen;
+use crate::spec::CircuitSpec;
+use halo2_base::halo2_proofs::{dev::VerifyFailure, halo2curves::bn256::Fr, plonk};
+use std::fmt;
+use std::ops::RangeInclusive;
+
+#[derive(Debug)]
+pub enum Error {
+    Halo2(plonk::Error),
+    Io(std::io::Error),
+    MockFailure(Vec<VerifyFailure>),
+    SpecVersion {
+        expected: u32,
+        found: u32,
+    },
+    ClaimMismatch {
+        claimed: Fr,
+        expected: Fr,
+    },
+    KMismatch {
+        expected: u32,
+        found: u32,
+    },
+    InvalidEncoding(String),
+    UnsupportedSpec(String),
+    SpecMismatch {
+        expected: CircuitSpec,
+        found: CircuitSpec,
+    },
+    Cancelled,
+    InstanceIndexOutOfRange {
+        row: usize,
+        len: usize,
+    },
+    UnsupportedBundleVersion {
+        found: u16,
+        supported: RangeInclusive<u16>,
+    },
+    BundleFailed {
+        index: usize,
+        source: Box<Error>,
+    },
+    VkMismatch {
+        vk_k: u32,
+        vk_instances: usize,
+        k: u32,
+        instances: usize,
+    },
+    SchemeMismatch {
+        expected: MultiOpen,
+        found: MultiOpen,
+    },
+}
+
+impl From<plonk::Error> for Error {
+    fn from(err: plonk::Error) -> Self {
+        Error::Halo2(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Halo2(err) => write!(f, "halo2: {err}"),
+            Error::Io(err) => write!(f, "io: {err}"),
+            Error::MockFailure(failures) => {
+                writeln!(f, "circuit is not satisfied:")?;
+                for failure in failures {
+                    writeln!(f, "  {failure}")?;
+                }
+                Ok(())
+            }
+            Error::SpecVersion { expected, found } => {
+                write!(f, "circuit spec version {found} does not match {expected}")
+            }
+            Error::ClaimMismatch { claimed, expected } => {
+                write!(f, "claimed output {claimed:?} does not match {expected:?}")
+            }
+            Error::KMismatch { expected, found } => {
+                write!(
+                    f,
+                    "proof was made with k = {found}, params have k = {expected}"
+                )
+            }
+            Error::InvalidEncoding(reason) => write!(f, "invalid encoding: {reason}"),
+            Error::UnsupportedSpec(reason) => write!(f, "unsupported circuit spec: {reason}"),
+            Error::SpecMismatch { expected, found } => {
+                write!(f, "bundle spec {found:?} does not match {expected:?}")
+            }
+            Error::Cancelled => write!(f, "proving was cancelled"),
+            Error::InstanceIndexOutOfRange { row, len } => {
+                write!(
+                    f,
+                    "instance row {row} is exposed but only {len} values were given"
+                )
+            }
+            Error::UnsupportedBundleVersion { found, supported } => {
+                write!(
+                    f,
+                    "bundle version {found} is outside the supported {}..={}",
+                    supported.start(),
+                    supported.end()
+                )
+            }
+            Error::BundleFailed { index, source } => write!(f, "bundle {index}: {source}"),
+            Error::VkMismatch {
+                vk_k,
+                vk_instances,
+                k,
+                instances,
+            } => write!(
+                f,
+                "verifying key is for k = {vk_k} with {vk_instances} instance columns, \
+                 bundle has k = {k} with {instances}"
+            ),
+            Error::SchemeMismatch { expected, found } => {
+                write!(
+                    f,
+                    "proof was made with {found:?}, verifier expects {expected:?}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::BundleFailed { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::error::Error;
+use halo2_base::halo2_proofs::halo2curves::FieldExt;
+use num_bigint::BigUint;
+use std::io::Write;
+
+pub(crate) fn to_biguint<F: FieldExt>(value: F) -> BigUint {
+    BigUint::from_bytes_le(value.to_repr().as_ref())
+}
+
+/// Writes F_1..=F_{fib_size} for seeds (a, b) to `writer` as CSV, one row at a time.
+///
+/// Columns are `index,value,wrapped`: the 1-based index, the field element as a decimal
+/// integer, and whether the integer sequence has reached the field modulus by that term, in
+/// which case `value` is the reduced residue. The integer sequence is carried alongside the
+/// field one only until it wraps, so nothing is collected in memory.
+pub fn export_sequence_csv<F: FieldExt>(
+    fib_size: usize,
+    seed_a: u64,
+    seed_b: u64,
+    mut writer: impl Write,
+) -> Result<(), Error> {
+    let modulus = to_biguint(-F::one()) + 1u32;
+
+    writeln!(writer, "index,value,wrapped")?;
+    let (mut a, mut b) = (F::from(seed_a), F::from(seed_b));
+    let (mut int_a, mut int_b) = (BigUint::from(seed_a), BigUint::from(seed_b));
+    let mut wrapped = false;
+    for index in 1..=fib_size {
+        // Both seeds are below the modulus and the terms only grow from there, so once one
+        // wraps every later one has too, and the integers need not be carried any further
+        wrapped = wrapped || int_a >= modulus;
+        writeln!(writer, "{index},{},{wrapped}", to_biguint(a))?;
+
+        let next = a + b;
+        a = b;
+        b = next;
+        if !wrapped {
+            let int_next = &int_a + &int_b;
+            int_a = std::mem::replace(&mut int_b, int_next);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn first_rows_and_wrap() {
+        let mut buffer = vec![];
+        export_sequence_csv::<Fr>(367, 1, 1, &mut buffer).unwrap();
+        let csv = String::from_utf8(buffer).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 368);
+        assert_eq!(
+            lines[..6],
+            [
+                "index,value,wrapped",
+                "1,1,false",
+                "2,1,false",
+                "3,2,false",
+                "4,3,false",
+                "5,5,false",
+            ]
+        );
+        assert_eq!(lines[10], "10,55,false");
+
+        // F_367 is the first term past the bn256 scalar modulus
+        assert!(lines[366].ends_with(",false"));
+        assert!(lines[367].starts_with("367,"));
+        assert!(lines[367].ends_with(",true"));
+
+        // Past the wrap only the flag is written, and it stays set
+        let mut buffer = vec![];
+        export_sequence_csv::<Fr>(2000, 1, 1, &mut buffer).unwrap();
+        let csv = String::from_utf8(buffer).unwrap();
+        let flags: Vec<_> = csv
+            .lines()
+            .skip(1)
+            .map(|line| line.ends_with(",true"))
+            .collect();
+        assert_eq!(flags.iter().position(|wrapped| *wrapped), Some(366));
+        assert!(flags[366..].iter().all(|wrapped| *wrapped));
+    }
+}
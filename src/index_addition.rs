@@ -0,0 +1,176 @@
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b, F_{m+n}]
+const SUM_ROW: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct IndexAdditionConfig {
+    pub fib: FibConfig,
+    /// F_m, F_{n+1}, F_{m-1}, F_n and F_{m+n} in one row
+    pub terms: [Column<Advice>; 5],
+    pub selector: Selector,
+}
+
+/// Checks F_{m+n} = F_m F_{n+1} + F_{m-1} F_n from the tails of two independent chains.
+///
+/// Both chains are `FibChip` runs from the same seeds: one to F_m, one to F_{n+1}. Their last
+/// two terms are copied into a single row next to the claimed F_{m+n}, where one degree-3
+/// gate applies the identity. Like the sum identity, it only holds for the seeds (1, 1).
+pub struct IndexAdditionChip<F: FieldExt> {
+    config: IndexAdditionConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> IndexAdditionChip<F> {
+    pub fn construct(config: IndexAdditionConfig) -> IndexAdditionChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_index_addition(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> IndexAdditionConfig {
+        let fib = FibChip::configure(advice, instance, cs);
+        let [col_a, col_b, col_c] = advice;
+        let extra = [cs.advice_column(), cs.advice_column()];
+        let terms = [col_a, col_b, col_c, extra[0], extra[1]];
+        let selector = cs.selector();
+
+        // The chain columns already have equality; the two new ones take copies too
+        for column in extra {
+            cs.enable_equality(column);
+        }
+
+        cs.create_gate("index addition", |cells| {
+            let s = cells.query_selector(selector);
+            let [f_m, f_n1, f_m_1, f_n, f_mn] =
+                terms.map(|column| cells.query_advice(column, Rotation::cur()));
+
+            vec![s * (f_m * f_n1 + f_m_1 * f_n - f_mn)]
+        });
+
+        IndexAdditionConfig {
+            fib,
+            terms,
+            selector,
+        }
+    }
+
+    /// Copies both chains' tails into the identity row and returns the F_{m+n} cell
+    pub fn assign_identity(
+        &self,
+        mut layouter: impl Layouter<F>,
+        m_tail: (&AssignedCell<F, F>, &AssignedCell<F, F>),
+        n_tail: (&AssignedCell<F, F>, &AssignedCell<F, F>),
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let (f_m_1, f_m) = m_tail;
+        let (f_n, f_n1) = n_tail;
+        let [col_m, col_n1, col_m_1, col_n, col_mn] = self.config.terms;
+
+        layouter.assign_region(
+            || "index addition",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                f_m.copy_advice(|| "F_m", &mut region, col_m, 0)?;
+                f_n1.copy_advice(|| "F_{n+1}", &mut region, col_n1, 0)?;
+                f_m_1.copy_advice(|| "F_{m-1}", &mut region, col_m_1, 0)?;
+                f_n.copy_advice(|| "F_n", &mut region, col_n, 0)?;
+
+                let sum =
+                    f_m.value().copied() * f_n1.value() + f_m_1.value().copied() * f_n.value();
+                region.assign_advice(|| "F_{m+n}", col_mn, 0, || sum)
+            },
+        )
+    }
+}
+
+/// Runs `chip` to F_{len} and returns the last two terms (F_{len-1}, F_len)
+fn chain_tail<F: FieldExt>(
+    chip: &FibChip<F>,
+    mut layouter: impl Layouter<F>,
+    len: usize,
+) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), Error> {
+    let (_, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+    for _ in 3..len {
+        let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+        b = c;
+        c = new_c;
+    }
+    Ok((b, c))
+}
+
+/// Proves the F_{m+n} at instance row 2 agrees with the addition identity
+#[derive(Clone, Debug, Default)]
+pub struct IndexAdditionCircuit {
+    pub m: usize,
+    pub n: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for IndexAdditionCircuit {
+    type Config = IndexAdditionConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        IndexAdditionChip::configure_index_addition(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // Both chains need at least the one row that yields F_3
+        if self.m < 3 || self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let fib = FibChip::construct(config.fib.clone());
+        let m_tail = chain_tail(&fib, layouter.namespace(|| "m chain"), self.m)?;
+        let n_tail = chain_tail(&fib, layouter.namespace(|| "n chain"), self.n + 1)?;
+
+        let chip = IndexAdditionChip::construct(config);
+        let sum = chip.assign_identity(
+            layouter.namespace(|| "identity"),
+            (&m_tail.0, &m_tail.1),
+            (&n_tail.0, &n_tail.1),
+        )?;
+        fib.expose_public(layouter.namespace(|| "F_{m+n}"), &sum, SUM_ROW)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn f_11_from_5_and_6() {
+        // F_11 = F_5 F_7 + F_4 F_6 = 5 * 13 + 3 * 8 = 89
+        let circuit = IndexAdditionCircuit { m: 5, n: 6 };
+        let one = Fr::from(1);
+
+        let prover =
+            MockProver::<Fr>::run(5, &circuit, vec![vec![one, one, Fr::from(89)]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let prover =
+            MockProver::<Fr>::run(5, &circuit, vec![vec![one, one, Fr::from(88)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
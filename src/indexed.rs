@@ -0,0 +1,266 @@
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b, i, F_i]
+const INDEX_ROW: usize = 2;
+const VALUE_ROW: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct IndexedConfig {
+    pub advice: [Column<Advice>; 3],
+    /// 1 on the rows up to and including the one holding F_i, 0 after
+    pub active: Column<Advice>,
+    /// Index of the last active term, 2 plus the running count of `active`
+    pub count: Column<Advice>,
+    /// The `c` of the last active row, carried down the rest of the region
+    pub value: Column<Advice>,
+    pub selector: Selector,
+    pub first: Selector,
+    pub step: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// Proves a public pair (i, v) has v = F_i, with i no larger than a fixed `max_index`.
+///
+/// The region always runs to `max_index`, so the shape of the circuit does not depend on i.
+/// Row r holds c = F_{r+3} and a boolean `active`, which starts at 1 and can only drop to 0.
+/// `count` adds up the active rows on top of the two seeds and `value` keeps the `c` of the
+/// last active one, so on the last row they are i and F_i and are checked against the instance.
+pub struct IndexedChip<F: FieldExt> {
+    config: IndexedConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> IndexedChip<F> {
+    pub fn construct(config: IndexedConfig) -> IndexedChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_indexed(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> IndexedConfig {
+        let [col_a, col_b, col_c] = advice;
+        let active = cs.advice_column();
+        let count = cs.advice_column();
+        let value = cs.advice_column();
+        let selector = cs.selector();
+        let first = cs.selector();
+        let step = cs.selector();
+
+        // Seeds come in from the instance, (i, F_i) go back out to it
+        cs.enable_equality(col_a);
+        cs.enable_equality(col_b);
+        cs.enable_equality(count);
+        cs.enable_equality(value);
+        cs.enable_equality(instance);
+
+        cs.create_gate("indexed", |cells| {
+            let s = cells.query_selector(selector);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let c = cells.query_advice(col_c, Rotation::cur());
+            let active = cells.query_advice(active, Rotation::cur());
+
+            vec![
+                s.clone() * (a + b - c),
+                s * active.clone() * (Expression::Constant(F::one()) - active),
+            ]
+        });
+
+        // F_3 is always counted, so i >= 3
+        cs.create_gate("indexed first", |cells| {
+            let s = cells.query_selector(first);
+            let one = Expression::Constant(F::one());
+            let c = cells.query_advice(col_c, Rotation::cur());
+            let active = cells.query_advice(active, Rotation::cur());
+            let count = cells.query_advice(count, Rotation::cur());
+            let value = cells.query_advice(value, Rotation::cur());
+
+            vec![
+                s.clone() * (active - one),
+                s.clone() * (count - Expression::Constant(F::from(3))),
+                s * (value - c),
+            ]
+        });
+
+        // The whole sequence lives in one region, so rows chain by rotation instead of copies
+        cs.create_gate("indexed step", |cells| {
+            let s = cells.query_selector(step);
+            let one = Expression::Constant(F::one());
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let c = cells.query_advice(col_c, Rotation::cur());
+            let b_prev = cells.query_advice(col_b, Rotation::prev());
+            let c_prev = cells.query_advice(col_c, Rotation::prev());
+            let [active_prev, count_prev, value_prev] =
+                [active, count, value].map(|column| cells.query_advice(column, Rotation::prev()));
+            let [active, count, value] =
+                [active, count, value].map(|column| cells.query_advice(column, Rotation::cur()));
+
+            vec![
+                s.clone() * (a - b_prev),
+                s.clone() * (b - c_prev),
+                // Once off, `active` stays off, so the active rows are a prefix
+                s.clone() * active.clone() * (one - active_prev),
+                s.clone() * (count - count_prev - active.clone()),
+                s * (value.clone() - value_prev.clone() - active * (c - value_prev)),
+            ]
+        });
+
+        IndexedConfig {
+            advice,
+            active,
+            count,
+            value,
+            selector,
+            first,
+            step,
+            instance,
+        }
+    }
+
+    /// Lays F_3..=F_{max_index} with the first `index - 2` rows active and returns the last
+    /// row's (count, value) cells, which hold (index, F_index)
+    pub fn assign_indexed(
+        &self,
+        mut layouter: impl Layouter<F>,
+        max_index: usize,
+        index: Value<usize>,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), Error> {
+        let config = &self.config;
+        if max_index < 3 {
+            return Err(Error::Synthesis);
+        }
+        let last = max_index - 3;
+
+        layouter.assign_region(
+            || "indexed",
+            |mut region| {
+                let mut a = region
+                    .assign_advice_from_instance(|| "a", config.instance, 0, config.advice[0], 0)?
+                    .value()
+                    .copied();
+                let mut b = region
+                    .assign_advice_from_instance(|| "b", config.instance, 1, config.advice[1], 0)?
+                    .value()
+                    .copied();
+
+                let mut count = Value::known(F::from(2));
+                let mut value = Value::known(F::zero());
+                let mut out = None;
+
+                for row in 0..=last {
+                    config.selector.enable(&mut region, row)?;
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else {
+                        config.step.enable(&mut region, row)?;
+                        region.assign_advice(|| "a", config.advice[0], row, || a)?;
+                        region.assign_advice(|| "b", config.advice[1], row, || b)?;
+                    }
+
+                    let c = a + b;
+                    region.assign_advice(|| "c", config.advice[2], row, || c)?;
+
+                    // Row r holds F_{r+3}
+                    let active = index.map(|index| F::from((row + 3 <= index) as u64));
+                    region.assign_advice(|| "active", config.active, row, || active)?;
+
+                    count = count + active;
+                    value = value + active * (c - value);
+                    let count_cell =
+                        region.assign_advice(|| "count", config.count, row, || count)?;
+                    let value_cell =
+                        region.assign_advice(|| "value", config.value, row, || value)?;
+                    if row == last {
+                        out = Some((count_cell, value_cell));
+                    }
+
+                    a = b;
+                    b = c;
+                }
+
+                out.ok_or(Error::Synthesis)
+            },
+        )
+    }
+}
+
+/// Proves the instance's (i, v) at rows 2 and 3 has v = F_i, for any 3 <= i <= `max_index`
+#[derive(Clone, Debug, Default)]
+pub struct IndexedCircuit {
+    pub max_index: usize,
+    pub index: Value<usize>,
+}
+
+impl IndexedCircuit {
+    pub fn new(max_index: usize, index: usize) -> Self {
+        Self {
+            max_index,
+            index: Value::known(index),
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for IndexedCircuit {
+    type Config = IndexedConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            max_index: self.max_index,
+            index: Value::unknown(),
+        }
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        IndexedChip::configure_indexed(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let instance = config.instance;
+        let chip = IndexedChip::construct(config);
+
+        let (index, value) =
+            chip.assign_indexed(layouter.namespace(|| "indexed"), self.max_index, self.index)?;
+        layouter.constrain_instance(index.cell(), instance, INDEX_ROW)?;
+        layouter.constrain_instance(value.cell(), instance, VALUE_ROW)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    fn run(index: usize, value: u64) -> MockProver<Fr> {
+        let circuit = IndexedCircuit::new(20, index);
+        let one = Fr::from(1);
+        let instance = vec![one, one, Fr::from(index as u64), Fr::from(value)];
+        MockProver::run(5, &circuit, vec![instance]).unwrap()
+    }
+
+    #[test]
+    fn index_and_value() {
+        assert_satisfied_verbose(&run(10, 55));
+        assert_satisfied_verbose(&run(20, 6765));
+
+        assert!(run(10, 56).verify().is_err());
+        // The right value under the wrong index
+        assert!(run(11, 55).verify().is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::cyclic::{CyclicChip, CyclicConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b, is_nonzero]
+const FLAG_ROW: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct IsNonzeroConfig {
+    pub value: Column<Advice>,
+    /// Inverse of the value where it is nonzero, anything otherwise
+    pub inv: Column<Advice>,
+    pub flag: Column<Advice>,
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// Reduces a cell to a boolean that is 1 exactly when the cell is nonzero.
+///
+/// The standard is-zero gadget with the output flipped: `value * inv = flag` and
+/// `value * (1 - flag) = 0`. A nonzero value forces `flag = 1`, and zero forces `flag = 0`
+/// whatever `inv` the prover picks. The flag is also constrained boolean, which the two
+/// already imply, so the intent reads off the gate.
+pub struct IsNonzeroChip<F: FieldExt> {
+    config: IsNonzeroConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> IsNonzeroChip<F> {
+    pub fn construct(config: IsNonzeroConfig) -> IsNonzeroChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_is_nonzero(
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> IsNonzeroConfig {
+        let value = cs.advice_column();
+        let inv = cs.advice_column();
+        let flag = cs.advice_column();
+        let selector = cs.selector();
+
+        // The value is copied in, the flag goes out to the instance
+        cs.enable_equality(value);
+        cs.enable_equality(flag);
+        cs.enable_equality(instance);
+
+        cs.create_gate("is nonzero", |cells| {
+            let s = cells.query_selector(selector);
+            let value = cells.query_advice(value, Rotation::cur());
+            let inv = cells.query_advice(inv, Rotation::cur());
+            let flag = cells.query_advice(flag, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * (value.clone() * inv - flag.clone()),
+                s.clone() * value * (one.clone() - flag.clone()),
+                s * flag.clone() * (one - flag),
+            ]
+        });
+
+        IsNonzeroConfig {
+            value,
+            inv,
+            flag,
+            selector,
+            instance,
+        }
+    }
+
+    /// Copies `value` in and returns the cell holding its nonzero flag
+    pub fn assign_is_nonzero(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = &self.config;
+
+        layouter.assign_region(
+            || "is nonzero",
+            |mut region| {
+                config.selector.enable(&mut region, 0)?;
+
+                let value = value.copy_advice(|| "value", &mut region, config.value, 0)?;
+                let inv = value.value().map(|v| v.invert().unwrap_or(F::zero()));
+                let flag = value
+                    .value()
+                    .map(|v| if *v == F::zero() { F::zero() } else { F::one() });
+                region.assign_advice(|| "inv", config.inv, 0, || inv)?;
+                region.assign_advice(|| "flag", config.flag, 0, || flag)
+            },
+        )
+    }
+
+    pub fn expose_flag(
+        &self,
+        mut layouter: impl Layouter<F>,
+        flag: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(flag.cell(), self.config.instance, FLAG_ROW)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NonzeroOutputConfig {
+    pub sequence: CyclicConfig,
+    pub is_nonzero: IsNonzeroConfig,
+}
+
+/// Runs `steps` steps of the sequence mod `modulus` from the seeds in the instance and exposes
+/// only whether the last term, x_{steps+1} mod m, is nonzero
+#[derive(Clone, Debug, Default)]
+pub struct NonzeroOutputCircuit {
+    pub modulus: u64,
+    pub steps: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for NonzeroOutputCircuit {
+    type Config = NonzeroOutputConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let instance = cs.instance_column();
+
+        NonzeroOutputConfig {
+            sequence: CyclicChip::configure_cyclic(instance, cs),
+            is_nonzero: IsNonzeroChip::configure_is_nonzero(instance, cs),
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let sequence = CyclicChip::construct(config.sequence);
+        sequence.load_table(layouter.namespace(|| "residue table"), self.modulus)?;
+        let (_, (_, last)) =
+            sequence.assign_steps(layouter.namespace(|| "sequence"), self.modulus, self.steps)?;
+
+        let chip = IsNonzeroChip::construct(config.is_nonzero);
+        let flag = chip.assign_is_nonzero(layouter.namespace(|| "is nonzero"), &last)?;
+        chip.expose_flag(layouter.namespace(|| "flag"), &flag)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    fn run(steps: usize, flag: u64) -> MockProver<Fr> {
+        let circuit = NonzeroOutputCircuit { modulus: 10, steps };
+        let instance = vec![Fr::from(0), Fr::from(1), Fr::from(flag)];
+        MockProver::run(6, &circuit, vec![instance]).unwrap()
+    }
+
+    #[test]
+    fn flags_zero_term() {
+        // From (F_0, F_1) = (0, 1), 14 steps end on F_15 = 610, which is 0 mod 10
+        assert_satisfied_verbose(&run(14, 0));
+        assert!(run(14, 1).verify().is_err());
+
+        // F_14 = 377 is 7 mod 10
+        assert_satisfied_verbose(&run(13, 1));
+        assert!(run(13, 0).verify().is_err());
+    }
+}
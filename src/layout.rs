@@ -0,0 +1,158 @@
+use crate::circuit::{rows_required, FibCircuit};
+use halo2_base::halo2_proofs::{
+    halo2curves::bn256::Fr,
+    plonk::{Any, Circuit, Column, ConstraintSystem},
+};
+
+/// One copy constraint: a (column, absolute row) cell on each side
+pub type CopyEdge = ((Column<Any>, usize), (Column<Any>, usize));
+
+/// The sequence layouts in this crate, by the circuit that uses them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutKind {
+    /// `FibCircuit`: one region per step
+    Unpacked,
+    /// `SumCircuit`: one region with the running sum alongside
+    Sum { check_identity: bool },
+    /// `MembershipCircuit` and `IndexKnowledgeCircuit`
+    Membership,
+    /// `RollupCircuit` carrying (F_1, F_2) through to (F_{n-1}, F_n)
+    Rollup,
+}
+
+/// Rows a `fib_size` sequence occupies in `layout`, computed from the assignment pattern alone.
+///
+/// - `Unpacked`: n - 2, one per term from F_3
+/// - `Sum`: n - 2, or n with `check_identity` for the two extra terms it compares against
+/// - `Membership`: n - 1, one per term from F_2
+/// - `Rollup`: n - 1, one per state
+///
+/// Every layout takes at least one row. Blinding rows are not included; see `min_k_for`.
+pub fn plan_rows(fib_size: usize, layout: LayoutKind) -> usize {
+    match layout {
+        LayoutKind::Unpacked => rows_required(fib_size),
+        LayoutKind::Sum {
+            check_identity: true,
+        } => fib_size.max(1),
+        LayoutKind::Sum {
+            check_identity: false,
+        } => rows_required(fib_size),
+        LayoutKind::Membership | LayoutKind::Rollup => fib_size.saturating_sub(1).max(1),
+    }
+}
+
+/// (advice column index, absolute row) of the cells assigned for one step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StepCells {
+    pub a: (usize, usize),
+    pub b: (usize, usize),
+    pub c: (usize, usize),
+}
+
+/// Where `FibCircuit` places every step, without running a prover.
+///
+/// Each step is its own one-row region over all three advice columns, so `SimpleFloorPlanner`
+/// stacks them back to back from row 0: step `i` occupies row `i`, with a, b and c in advice
+/// columns 0, 1 and 2.
+pub fn layout_map(fib_size: usize) -> Vec<StepCells> {
+    if fib_size < 3 {
+        return vec![];
+    }
+
+    (0..rows_required(fib_size))
+        .map(|row| StepCells {
+            a: (0, row),
+            b: (1, row),
+            c: (2, row),
+        })
+        .collect()
+}
+
+/// The copy constraints `FibCircuit` places for a `fib_size` sequence, derived from
+/// `layout_map` rather than read back out of a prover.
+///
+/// The seeds are copied from instance rows 0 and 1 into the first step's a and b, and every
+/// later step copies the previous b into its a and the previous c into its b. The output
+/// exposure is left out, as `FibCircuit` only adds it with `expose_output`.
+pub fn copy_graph(fib_size: usize) -> Vec<CopyEdge> {
+    let mut cs = ConstraintSystem::<Fr>::default();
+    let config = <FibCircuit as Circuit<Fr>>::configure(&mut cs);
+    let [a, b, c] = config.advice.map(Column::<Any>::from);
+    let instance = Column::<Any>::from(config.instance);
+
+    let steps = layout_map(fib_size);
+    if steps.is_empty() {
+        return vec![];
+    }
+
+    let mut edges = vec![((instance, 0), (a, 0)), ((instance, 1), (b, 0))];
+    for pair in steps.windows(2) {
+        let (prev, next) = (pair[0], pair[1]);
+        edges.push(((b, prev.b.1), (a, next.a.1)));
+        edges.push(((c, prev.c.1), (b, next.b.1)));
+    }
+    edges
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn first_and_last_steps() {
+        let steps = layout_map(10);
+        assert_eq!(steps.len(), 8);
+        assert_eq!(
+            steps[0],
+            StepCells {
+                a: (0, 0),
+                b: (1, 0),
+                c: (2, 0),
+            }
+        );
+        assert_eq!(steps[7].c, (2, 7));
+        assert!(layout_map(2).is_empty());
+    }
+
+    #[test]
+    fn copies_chain_rows() {
+        let mut cs = ConstraintSystem::<Fr>::default();
+        let config = <FibCircuit as Circuit<Fr>>::configure(&mut cs);
+        let [a, b, c] = config.advice.map(Column::<Any>::from);
+
+        let edges = copy_graph(10);
+        assert_eq!(edges.len(), 2 + 2 * 7);
+        assert_eq!(edges[0], ((config.instance.into(), 0), (a, 0)));
+        assert_eq!(edges[2], ((b, 0), (a, 1)));
+        assert_eq!(edges[3], ((c, 0), (b, 1)));
+        assert_eq!(edges[15], ((c, 6), (b, 7)));
+        assert!(copy_graph(2).is_empty());
+    }
+
+    #[test]
+    fn planned_rows() {
+        let n = 1000;
+        assert_eq!(plan_rows(n, LayoutKind::Unpacked), n - 2);
+        assert_eq!(plan_rows(n, LayoutKind::Unpacked), layout_map(n).len());
+        assert_eq!(
+            plan_rows(
+                n,
+                LayoutKind::Sum {
+                    check_identity: false
+                }
+            ),
+            n - 2
+        );
+        assert_eq!(
+            plan_rows(
+                n,
+                LayoutKind::Sum {
+                    check_identity: true
+                }
+            ),
+            n
+        );
+        assert_eq!(plan_rows(n, LayoutKind::Membership), n - 1);
+        assert_eq!(plan_rows(n, LayoutKind::Rollup), n - 1);
+    }
+}
@@ -1,82 +1,486 @@
 #![allow(clippy::type_complexity)]
 use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::marker::PhantomData;
 
+pub mod binding;
+pub mod cache;
+pub mod ceiling;
+pub mod circuit;
+pub mod cost;
+pub mod cyclic;
+pub mod digest;
+pub mod digits;
+pub mod divergence;
+pub mod divisibility;
+pub mod doubling;
+pub mod encoding;
+pub mod error;
+pub mod export;
+pub mod index_addition;
+pub mod indexed;
+pub mod is_nonzero;
+pub mod layout;
+pub mod membership;
+pub mod merkle;
+pub mod mod_step;
+pub mod negative;
+pub mod non_membership;
+pub mod padded;
+pub mod pedersen;
+pub mod pisano;
+pub mod predicate;
+pub mod private_seeds;
+#[cfg(feature = "profiling")]
+pub mod profiling;
+pub mod prover;
+pub mod receipt;
+pub mod rollup;
+pub mod seeds;
+#[cfg(feature = "prover")]
+pub mod session;
+pub mod single_column;
+pub mod source;
+pub mod spec;
+pub mod streaming;
+pub mod sum_squares;
+pub mod telemetry;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
+pub mod trace;
+pub mod verify_only;
+pub mod vk_diff;
+pub mod window;
+#[cfg(feature = "prover")]
+pub mod witness;
+pub mod zeckendorf;
+
 #[derive(Debug, Clone)]
 pub struct FibConfig {
     pub advice: [Column<Advice>; 3],
     pub selector: Selector,
     pub instance: Column<Instance>,
+    pub sum: Option<SumConfig>,
+    pub phased: Option<PhasedConfig>,
+    pub split: Option<SplitSelectors>,
+}
+
+/// Separate first-row and interior selectors laid by `FibChip::configure_split`
+#[derive(Debug, Clone)]
+pub struct SplitSelectors {
+    pub first_row_selector: Selector,
+    /// Also `FibConfig::selector`, so layouts that only know the one selector gate steps
+    pub step_selector: Selector,
+}
+
+/// Running-sum column laid alongside the sequence by `FibChip::configure_with_sum`
+#[derive(Debug, Clone)]
+pub struct SumConfig {
+    pub column: Column<Advice>,
+    pub first: Selector,
+    pub step: Selector,
+    pub identity: Selector,
+}
+
+/// Where the chip finds the seeds and puts the output in the instance column.
+///
+/// The chip works in logical rows, 0 and 1 for the seeds and 2 for the output, and the layout
+/// maps each to the instance row a verifier expects it at. Logical rows past 2 pass through.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InstanceLayout {
+    /// [a, b, out]
+    #[default]
+    SeedsFirst,
+    /// [out, a, b]. The output row is kept even when nothing is exposed there
+    OutputFirst,
+    /// `map[i]` is the instance row for logical row i
+    Custom(Vec<usize>),
+}
+
+impl InstanceLayout {
+    pub fn row(&self, logical: usize) -> usize {
+        match self {
+            InstanceLayout::SeedsFirst => logical,
+            InstanceLayout::OutputFirst => match logical {
+                0 | 1 => logical + 1,
+                2 => 0,
+                row => row,
+            },
+            InstanceLayout::Custom(map) => map.get(logical).copied().unwrap_or(logical),
+        }
+    }
+}
+
+/// Second-phase column laid alongside the sequence by `FibChip::configure_phased`
+#[derive(Debug, Clone)]
+pub struct PhasedConfig {
+    pub column: Column<Advice>,
+    pub challenge: Challenge,
+    pub mix: Selector,
 }
 
 pub struct FibChip<F: FieldExt> {
     config: FibConfig,
+    layout: InstanceLayout,
+    // Times the add gate was enabled, for `check_selector_enabled`
+    enabled: Cell<usize>,
+    // Test-only replacement `c` for one `assign_row` call, counted from 0
+    #[cfg(test)]
+    c_override: Cell<Option<(usize, F)>>,
+    #[cfg(test)]
+    rows_assigned: Cell<usize>,
+    // Test-only replacement `c` for the first row
+    #[cfg(test)]
+    first_c_override: Cell<Option<F>>,
     _marker: PhantomData<F>,
 }
 
 impl<F: FieldExt> FibChip<F> {
     pub fn construct(config: FibConfig) -> FibChip<F> {
+        Self::construct_with_layout(config, InstanceLayout::default())
+    }
+
+    pub fn construct_with_layout(config: FibConfig, layout: InstanceLayout) -> FibChip<F> {
         Self {
             config,
+            layout,
+            enabled: Cell::new(0),
+            #[cfg(test)]
+            c_override: Cell::new(None),
+            #[cfg(test)]
+            rows_assigned: Cell::new(0),
+            #[cfg(test)]
+            first_c_override: Cell::new(None),
             _marker: PhantomData,
         }
     }
 
+    /// Enables the add gate at `offset`, counting it for `check_selector_enabled`
+    pub fn enable_selector(&self, region: &mut Region<'_, F>, offset: usize) -> Result<(), Error> {
+        self.enabled.set(self.enabled.get() + 1);
+        self.config.selector.enable(region, offset)
+    }
+
+    /// Enables the add gate on a sequence's first row: the first-row selector when configured
+    /// with `configure_split`, the one shared selector otherwise
+    pub fn enable_first_row_selector(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+    ) -> Result<(), Error> {
+        match &self.config.split {
+            Some(split) => {
+                self.enabled.set(self.enabled.get() + 1);
+                split.first_row_selector.enable(region, offset)
+            }
+            None => self.enable_selector(region, offset),
+        }
+    }
+
+    /// Debug-build guard that a layout of `steps` rows enabled the add gate at all.
+    ///
+    /// A row without its selector leaves a + b = c unchecked, so a layout that never enables it
+    /// accepts any witness. The count comes from `enable_selector`, which the floor planner may
+    /// run more than once per row, so only a count of zero is reported, as `Error::Synthesis`.
+    pub fn check_selector_enabled(&self, steps: usize) -> Result<(), Error> {
+        if cfg!(debug_assertions) && steps > 0 && self.enabled.get() == 0 {
+            log::error!("add gate never enabled over {steps} steps");
+            return Err(Error::Synthesis);
+        }
+        Ok(())
+    }
+
+    /// Debug-build guard that a row's known values satisfy a + b = c.
+    ///
+    /// A variant that assigns inconsistent cells, or a copy that equality does not enforce,
+    /// is reported at synthesis as `Error::Synthesis` instead of as an unsatisfied gate.
+    pub fn check_row(a: Value<&F>, b: Value<&F>, c: Value<&F>) -> Result<(), Error> {
+        if cfg!(debug_assertions) {
+            let mut consistent = true;
+            a.zip(b)
+                .zip(c)
+                .map(|((a, b), c)| consistent = *a + *b == *c);
+            if !consistent {
+                log::error!("row breaks a + b = c");
+                return Err(Error::Synthesis);
+            }
+        }
+        Ok(())
+    }
+
     // Define a custom gate here
     pub fn configure(
         advice: [Column<Advice>; 3],
         instance: Column<Instance>,
         cs: &mut ConstraintSystem<F>,
     ) -> FibConfig {
-        let col_a = advice[0];
-        let col_b = advice[1];
-        let col_c = advice[2];
         let selector = cs.selector();
+        Self::configure_shared(advice, instance, selector, cs)
+    }
 
-        // Every column that will be checked against a value in another column
-        // needs to be enabled for equality
-        cs.enable_equality(col_a);
-        cs.enable_equality(col_b);
-        cs.enable_equality(col_c);
-        cs.enable_equality(instance);
+    /// `configure` with a selector owned by the caller, so composed chips can share one.
+    ///
+    /// Every gate keyed on a selector fires on every row it is enabled, so each sharing chip's
+    /// gate also runs on the other's rows. That is sound when the chips use disjoint advice
+    /// columns (a row the gate does not own reads unassigned cells, which are zero and satisfy
+    /// a + b = c), or when they share the same columns and the same relation.
+    pub fn configure_shared(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        selector: Selector,
+        cs: &mut ConstraintSystem<F>,
+    ) -> FibConfig {
+        Self::configure_gate(
+            advice,
+            instance,
+            selector,
+            [true; 3],
+            cs,
+            "add",
+            Self::add_gate(advice),
+        )
+    }
 
-        cs.create_gate("add", |cells| {
-            let s = cells.query_selector(selector);
+    /// `configure` enabling equality only on the advice columns flagged in `equality`.
+    ///
+    /// Each column with equality enabled joins the permutation argument, which costs a commitment
+    /// and an evaluation or so in every proof. `assign_first_row` and `assign_row` copy into all
+    /// three columns, so this is for layouts that assign the gate's rows themselves and never
+    /// copy into or out of some column. The instance column always keeps equality.
+    pub fn configure_with_equality(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        equality: [bool; 3],
+        cs: &mut ConstraintSystem<F>,
+    ) -> FibConfig {
+        let selector = cs.selector();
+        Self::configure_gate(
+            advice,
+            instance,
+            selector,
+            equality,
+            cs,
+            "add",
+            Self::add_gate(advice),
+        )
+    }
+
+    /// `configure` with the add gate replaced by the constraints `expr_fn` returns, each
+    /// multiplied by `FibConfig::selector`.
+    ///
+    /// The closure gets the gate's `VirtualCells` and queries the columns it captured from
+    /// `advice`. Every row the chip assigns is its own one-row region laid out as
+    /// `[a, b, c]` in `advice[0..3]`, so `Rotation::cur()` is the only rotation that stays
+    /// within a row; neighbouring rows belong to other regions and the floor planner gives no
+    /// guarantee about what sits there. The row's witness is still `c = a + b` from
+    /// `assign_row`, so a gate relating the columns some other way needs its `c` supplied
+    /// through `assign_row_with`.
+    pub fn configure_custom(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+        expr_fn: impl Fn(&mut VirtualCells<'_, F>) -> Vec<Expression<F>>,
+    ) -> FibConfig {
+        let selector = cs.selector();
+        Self::configure_gate(advice, instance, selector, [true; 3], cs, "custom", expr_fn)
+    }
 
+    // The built-in a + b = c, before the selector is applied
+    fn add_gate(
+        advice: [Column<Advice>; 3],
+    ) -> impl Fn(&mut VirtualCells<'_, F>) -> Vec<Expression<F>> {
+        let [col_a, col_b, col_c] = advice;
+        move |cells| {
             // Rotation::cur() is just a helper for 1 (any isize offset is allowed)
             // Boring rotations are better for performance
             let a = cells.query_advice(col_a, Rotation::cur());
             let b = cells.query_advice(col_b, Rotation::cur());
             let c = cells.query_advice(col_c, Rotation::cur());
 
+            vec![a + b - c]
+        }
+    }
+
+    fn configure_gate(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        selector: Selector,
+        equality: [bool; 3],
+        cs: &mut ConstraintSystem<F>,
+        name: &'static str,
+        gate: impl Fn(&mut VirtualCells<'_, F>) -> Vec<Expression<F>>,
+    ) -> FibConfig {
+        // Every column that will be checked against a value in another column
+        // needs to be enabled for equality
+        for (column, enable) in advice.into_iter().zip(equality) {
+            if enable {
+                cs.enable_equality(column);
+            }
+        }
+        cs.enable_equality(instance);
+
+        cs.create_gate(name, |cells| {
+            let s = cells.query_selector(selector);
+
             // If s = 0 (not turned on selector) - a, b, c can be anything and gate will still be 0
             // If s = 1 (turned on selector), the constraint needs to equal 0
-            vec![s * (a + b - c)]
+            gate(cells)
+                .into_iter()
+                .map(|expr| s.clone() * expr)
+                .collect::<Vec<_>>()
         });
 
         FibConfig {
-            advice: [col_a, col_b, col_c],
+            advice,
             selector,
             instance,
+            sum: None,
+            phased: None,
+            split: None,
         }
     }
 
+    /// `configure` with the add gate keyed on two selectors, one enabled on the first row and
+    /// one on every later row.
+    ///
+    /// Both carry a + b = c, so the sequence is checked as before, but a caller can key extra
+    /// gates on `SplitSelectors::first_row_selector` (a seed commitment, say) and have them
+    /// apply to the first row only.
+    pub fn configure_split(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> FibConfig {
+        let step_selector = cs.selector();
+        let mut config = Self::configure_shared(advice, instance, step_selector, cs);
+        let [col_a, col_b, col_c] = advice;
+        let first_row_selector = cs.selector();
+
+        cs.create_gate("add first row", |cells| {
+            let s = cells.query_selector(first_row_selector);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let c = cells.query_advice(col_c, Rotation::cur());
+
+            vec![s * (a + b - c)]
+        });
+
+        config.split = Some(SplitSelectors {
+            first_row_selector,
+            step_selector,
+        });
+        config
+    }
+
+    // Same gate as `configure`, plus a fourth column accumulating F_1 + ... + F_i row by row
+    pub fn configure_with_sum(
+        advice: [Column<Advice>; 3],
+        sum: Column<Advice>,
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> FibConfig {
+        let mut config = Self::configure(advice, instance, cs);
+        let [col_a, col_b, col_c] = advice;
+        let first = cs.selector();
+        let step = cs.selector();
+        let identity = cs.selector();
+
+        cs.enable_equality(sum);
+
+        cs.create_gate("sum first", |cells| {
+            let s = cells.query_selector(first);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let c = cells.query_advice(col_c, Rotation::cur());
+            let total = cells.query_advice(sum, Rotation::cur());
+
+            vec![s * (a + b + c - total)]
+        });
+
+        // The sum rows share one region, so the previous total is a rotation away
+        cs.create_gate("sum step", |cells| {
+            let s = cells.query_selector(step);
+            let c = cells.query_advice(col_c, Rotation::cur());
+            let prev = cells.query_advice(sum, Rotation::prev());
+            let total = cells.query_advice(sum, Rotation::cur());
+
+            vec![s * (prev + c - total)]
+        });
+
+        // sum_{i=1}^{n} F_i = F_{n+2} - 1, where F_{n+2} is the `c` two rows down
+        cs.create_gate("sum identity", |cells| {
+            let s = cells.query_selector(identity);
+            let total = cells.query_advice(sum, Rotation::cur());
+            let f_n_2 = cells.query_advice(col_c, Rotation(2));
+
+            vec![s * (total + Expression::Constant(F::one()) - f_n_2)]
+        });
+
+        config.sum = Some(SumConfig {
+            column: sum,
+            first,
+            step,
+            identity,
+        });
+        config
+    }
+
+    /// Same gate as `configure`, plus one advice column in the second phase and a challenge
+    /// drawn after the first.
+    ///
+    /// The sequence columns are committed first, the verifier's challenge r is squeezed from
+    /// the transcript, and only then is the second-phase column assigned, so a gate over it can
+    /// use r as randomness the prover could not anticipate. For now the only such gate is
+    /// `mixed = a + r * b`, a random linear combination of a row's two inputs, as wiring for
+    /// lookup-style arguments to come.
+    pub fn configure_phased(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> FibConfig {
+        let mut config = Self::configure(advice, instance, cs);
+        let [col_a, col_b, _] = advice;
+        let challenge = cs.challenge_usable_after(FirstPhase);
+        let column = cs.advice_column_in(SecondPhase);
+        let mix = cs.selector();
+
+        cs.enable_equality(column);
+
+        cs.create_gate("mix", |cells| {
+            let s = cells.query_selector(mix);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let mixed = cells.query_advice(column, Rotation::cur());
+            let r = cells.query_challenge(challenge);
+
+            vec![s * (a + r * b - mixed)]
+        });
+
+        config.phased = Some(PhasedConfig {
+            column,
+            challenge,
+            mix,
+        });
+        config
+    }
+
     pub fn assign_first_row(
         &self,
         mut layouter: impl Layouter<F>,
     ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>, AssignedCell<F, F>), Error> {
-        layouter.assign_region(
+        let cells = layouter.assign_region(
             || "first row",
             |mut region| {
                 // Even the first row needs to match formula of gate
-                self.config.selector.enable(&mut region, 0)?;
+                self.enable_first_row_selector(&mut region, 0)?;
 
                 // Copies values from advice provider, we can only work with values in the advice
                 let a_cell = region.assign_advice_from_instance(
                     || "a",
                     self.config.instance,
                     // Advice is a static column, so we can use absolute row offsets (basically just the vec![] we pass to prover)
-                    0,
+                    self.layout.row(0),
                     self.config.advice[0],
                     0,
                 )?;
@@ -85,7 +489,7 @@ impl<F: FieldExt> FibChip<F> {
                     || "b",
                     self.config.instance,
                     // Advice is a static coumn, so we can use absolute row offsets
-                    1,
+                    self.layout.row(1),
                     self.config.advice[1],
                     0,
                 )?;
@@ -93,71 +497,325 @@ impl<F: FieldExt> FibChip<F> {
                 println!("a: {:?}", a_cell.value());
                 println!("b: {:?}", b_cell.value());
 
-                let c_cell = region.assign_advice(
-                    || "c",
-                    self.config.advice[2],
-                    0,
-                    || {
-                        a_cell
-                            .value()
-                            .and_then(|a| b_cell.value().and_then(|b| Value::known(*a + *b)))
-                    },
-                )?;
+                #[allow(unused_mut)]
+                let mut c_value = a_cell
+                    .value()
+                    .and_then(|a| b_cell.value().and_then(|b| Value::known(*a + *b)));
+
+                #[cfg(test)]
+                {
+                    if let Some(c) = self.first_c_override.get() {
+                        c_value = Value::known(c);
+                    }
+                }
 
+                let c_cell = region.assign_advice(|| "c", self.config.advice[2], 0, || c_value)?;
+
+                Self::check_row(a_cell.value(), b_cell.value(), c_cell.value())?;
                 Ok((a_cell, b_cell, c_cell))
             },
-        )
+        )?;
+
+        telemetry::record(1, 2);
+        Ok(cells)
+    }
+
+    // Same as `assign_first_row`, but the seeds are copied from cells assigned by an upstream
+    // region instead of being read from the instance column
+    pub fn assign_first_row_from_cells(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>, AssignedCell<F, F>), Error> {
+        let cells = layouter.assign_region(
+            || "first row",
+            |mut region| {
+                self.enable_first_row_selector(&mut region, 0)?;
+
+                // The copies tie the seeds to wherever the caller computed them
+                let a_cell = a.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                let b_cell = b.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_value = a_cell.value().and_then(|a| b_cell.value().map(|b| *a + *b));
+                let c_cell = region.assign_advice(|| "c", self.config.advice[2], 0, || c_value)?;
+
+                Self::check_row(a_cell.value(), b_cell.value(), c_cell.value())?;
+                Ok((a_cell, b_cell, c_cell))
+            },
+        )?;
+
+        telemetry::record(1, 2);
+        Ok(cells)
+    }
+
+    /// Runs a `fib_size` sequence whose seeds come from `seed_fn`, called once at synthesis
+    /// time, and returns the cell holding F_{fib_size}. The seeds are private witnesses.
+    pub fn run_lazy(
+        &self,
+        mut layouter: impl Layouter<F>,
+        fib_size: usize,
+        mut seed_fn: impl FnMut() -> (F, F),
+    ) -> Result<AssignedCell<F, F>, Error> {
+        if fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        // Outside the region, which the floor planner may run more than once
+        let (a, b) = seed_fn();
+        let (a, b) = layouter.assign_region(
+            || "lazy seeds",
+            |mut region| {
+                let a =
+                    region.assign_advice(|| "a", self.config.advice[0], 0, || Value::known(a))?;
+                let b =
+                    region.assign_advice(|| "b", self.config.advice[1], 0, || Value::known(b))?;
+                Ok((a, b))
+            },
+        )?;
+
+        let (_, mut b, mut c) =
+            self.assign_first_row_from_cells(layouter.namespace(|| "first row"), &a, &b)?;
+        for _ in 3..fib_size {
+            let new_c = self.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            b = c;
+            c = new_c;
+        }
+        Ok(c)
     }
 
     pub fn assign_row(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        #[allow(unused_mut)]
+        let mut c_value = a.value().and_then(|a| b.value().map(|b| *a + *b));
+
+        #[cfg(test)]
+        {
+            let step = self.rows_assigned.replace(self.rows_assigned.get() + 1);
+            if let Some((target, c)) = self.c_override.get() {
+                if target == step {
+                    c_value = Value::known(c);
+                }
+            }
+        }
+
+        self.assign_next_row(layouter, a, b, c_value)
+    }
+
+    /// Makes the `step`-th `assign_row` call, counting from 0, witness `c` instead of a + b
+    #[cfg(test)]
+    pub(crate) fn override_c(&self, step: usize, c: F) {
+        self.c_override.set(Some((step, c)));
+    }
+
+    /// Makes `assign_first_row` witness `c` instead of a + b
+    #[cfg(test)]
+    pub(crate) fn override_first_c(&self, c: F) {
+        self.first_c_override.set(Some(c));
+    }
+
+    /// `assign_row` with `c` supplied by the caller, e.g. from a stored witness, instead of
+    /// added up here. The gate still checks it, and debug builds run `check_row` on it first.
+    pub fn assign_row_with(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+        c_value: Value<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        Self::check_row(a.value(), b.value(), c_value.as_ref())?;
+        self.assign_next_row(layouter, a, b, c_value)
+    }
+
+    // `assign_row`'s own c is a + b unless a test overrides it to reach the gate, so only
+    // `assign_row_with` checks it
+    fn assign_next_row(
         &self,
         mut layouter: impl Layouter<F>,
         a: &AssignedCell<F, F>,
         b: &AssignedCell<F, F>,
+        c_value: Value<F>,
     ) -> Result<AssignedCell<F, F>, Error> {
-        layouter.assign_region(
+        let c = layouter.assign_region(
             || "next_row",
             |mut region| {
-                self.config.selector.enable(&mut region, 0)?;
+                self.enable_selector(&mut region, 0)?;
 
                 // Copies the value from an assigned cell to another cell
                 // THIS IS A CONSTRAINT TOO - this ensures that each row follows the other!
                 a.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
                 b.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
 
-                let c_value = a.value().and_then(|a| b.value().map(|b| *a + *b));
-
                 let c = region.assign_advice(|| "c", self.config.advice[2], 0, || c_value)?;
 
                 // We return C from the region, this is how we can access region values outside of a region!
                 Ok(c)
             },
+        )?;
+
+        telemetry::record(1, 2);
+        Ok(c)
+    }
+
+    /// Assigns F_1..=F_n in a single region with the running sum, returning the cell for the
+    /// sum of the first `n` terms.
+    ///
+    /// With `check_identity` two more terms are computed so the total can be constrained
+    /// against F_{n+2} - 1. The identity only holds for the standard seeds (1, 1).
+    pub fn assign_with_sum(
+        &self,
+        mut layouter: impl Layouter<F>,
+        n: usize,
+        check_identity: bool,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let sum = self.config.sum.as_ref().ok_or(Error::Synthesis)?;
+        if n < 3 {
+            return Err(Error::Synthesis);
+        }
+        let rows = if check_identity { n } else { n - 2 };
+
+        layouter.assign_region(
+            || "sum",
+            |mut region| {
+                let mut a = region.assign_advice_from_instance(
+                    || "a",
+                    self.config.instance,
+                    self.layout.row(0),
+                    self.config.advice[0],
+                    0,
+                )?;
+                let mut b = region.assign_advice_from_instance(
+                    || "b",
+                    self.config.instance,
+                    self.layout.row(1),
+                    self.config.advice[1],
+                    0,
+                )?;
+                let mut total = Value::known(F::zero());
+                let mut out = None;
+
+                for row in 0..rows {
+                    if row == 0 {
+                        self.enable_first_row_selector(&mut region, row)?;
+                    } else {
+                        self.enable_selector(&mut region, row)?;
+                    }
+
+                    let c_value = a.value().and_then(|a| b.value().map(|b| *a + *b));
+                    let c = region.assign_advice(|| "c", self.config.advice[2], row, || c_value)?;
+
+                    if row == 0 {
+                        sum.first.enable(&mut region, row)?;
+                        total = a.value().copied() + b.value().copied() + c_value;
+                    } else {
+                        sum.step.enable(&mut region, row)?;
+                        total = total + c_value;
+                    }
+                    let total_cell = region.assign_advice(|| "sum", sum.column, row, || total)?;
+
+                    // Row r holds F_{r+3}, so the first n terms are summed at row n - 3
+                    if row == n - 3 {
+                        if check_identity {
+                            sum.identity.enable(&mut region, row)?;
+                        }
+                        out = Some(total_cell);
+                    }
+
+                    if row + 1 < rows {
+                        a = b.copy_advice(|| "a", &mut region, self.config.advice[0], row + 1)?;
+                        b = c.copy_advice(|| "b", &mut region, self.config.advice[1], row + 1)?;
+                    }
+                }
+
+                out.ok_or(Error::Synthesis)
+            },
         )
     }
 
-    // Instance is global
+    /// Copies `a` and `b` into one row and returns the second-phase cell holding a + r * b.
+    /// During the first phase the challenge, and so the cell, is still unknown.
+    pub fn assign_mix(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let phased = self.config.phased.as_ref().ok_or(Error::Synthesis)?;
+        let r = layouter.get_challenge(phased.challenge);
+
+        layouter.assign_region(
+            || "mix",
+            |mut region| {
+                phased.mix.enable(&mut region, 0)?;
+                let a = a.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                let b = b.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let mixed = a.value().copied() + r * b.value();
+                region.assign_advice(|| "mixed", phased.column, 0, || mixed)
+            },
+        )
+    }
+
+    // Instance is global; `row` is logical and goes through the chip's layout
     pub fn expose_public(
         &self,
         mut layouter: impl Layouter<F>,
         cell: &AssignedCell<F, F>,
         row: usize,
     ) -> Result<(), Error> {
-        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+        layouter.constrain_instance(cell.cell(), self.config.instance, self.layout.row(row))
+    }
+
+    /// `expose_public` when `row` is set; with `None` the cell stays internal, for a chip
+    /// embedded in a larger circuit that consumes the output itself
+    pub fn try_expose_public(
+        &self,
+        layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: Option<usize>,
+    ) -> Result<(), Error> {
+        match row {
+            Some(row) => self.expose_public(layouter, cell, row),
+            None => Ok(()),
+        }
+    }
+
+    // Exposes two consecutive terms (F_{n+1}, F_n) at `row` and `row + 1`, so a verifier can
+    // read the convergent F_{n+1} / F_n off the instance
+    pub fn expose_pair(
+        &self,
+        mut layouter: impl Layouter<F>,
+        next: &AssignedCell<F, F>,
+        cur: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        let instance = self.config.instance;
+        layouter.constrain_instance(next.cell(), instance, self.layout.row(row))?;
+        layouter.constrain_instance(cur.cell(), instance, self.layout.row(row + 1))
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::circuit::{compute, precompute_sequence, FibCircuit};
+    use crate::testing::assert_satisfied_verbose;
+    use crate::witness::StoredWitnessCircuit;
     use halo2_base::halo2_proofs::dev::MockProver;
     use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
 
+    // Seeds come from a witness region rather than the instance; only the output is public
     #[derive(Default)]
-    struct MyCircuit {
+    struct UpstreamCircuit {
+        seeds: (u64, u64),
         fib_size: usize,
     }
 
-    impl<F: FieldExt> Circuit<F> for MyCircuit {
+    impl Circuit<Fr> for UpstreamCircuit {
         type Config = FibConfig;
         type FloorPlanner = SimpleFloorPlanner;
 
@@ -165,42 +823,245 @@ mod test {
             Self::default()
         }
 
-        // Circuit setup (doesn't change on input)
-        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
-            let col_a = cs.advice_column();
-            let col_b = cs.advice_column();
-            let col_c = cs.advice_column();
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
             let instance = cs.instance_column();
 
-            FibChip::configure([col_a, col_b, col_c], instance, cs)
+            FibChip::configure(advice, instance, cs)
         }
 
-        // Changes for each proof
         fn synthesize(
             &self,
             config: Self::Config,
-            mut layouter: impl Layouter<F>,
+            mut layouter: impl Layouter<Fr>,
         ) -> Result<(), Error> {
-            let chip = FibChip::construct(config);
+            let (a, b) = layouter.assign_region(
+                || "upstream",
+                |mut region| {
+                    let a = region.assign_advice(
+                        || "seed a",
+                        config.advice[0],
+                        0,
+                        || Value::known(Fr::from(self.seeds.0)),
+                    )?;
+                    let b = region.assign_advice(
+                        || "seed b",
+                        config.advice[1],
+                        0,
+                        || Value::known(Fr::from(self.seeds.1)),
+                    )?;
+                    Ok((a, b))
+                },
+            )?;
 
-            let (_, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
-
-            // We've skipped the first 2 items in fib sequence (as they are awkward)
-            // 0 should be 3, just using 0 to test row count - 57 should be =9
-            for _ in 3..=self.fib_size - 1 {
+            let chip = FibChip::construct(config);
+            let (_, mut b, mut c) =
+                chip.assign_first_row_from_cells(layouter.namespace(|| "first row"), &a, &b)?;
+            for _ in 3..self.fib_size {
                 let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
                 b = c;
                 c = new_c;
             }
 
-            println!("c: {:?}", c.value());
+            chip.expose_public(layouter.namespace(|| "out"), &c, 0)
+        }
+    }
+
+    #[test]
+    fn seeds_from_upstream_cells() {
+        let circuit = UpstreamCircuit {
+            seeds: (2, 3),
+            fib_size: 10,
+        };
+        let out = compute(10, Fr::from(2), Fr::from(3));
 
-            // chip.expose_public(layouter.namespace(|| "out"), &c, 2)?;
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![vec![out]]).unwrap();
+        assert_satisfied_verbose(&prover);
 
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![vec![out + Fr::from(1)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // Seeds are read through a closure over a cell the test can change between runs
+    #[derive(Default)]
+    struct LazyCircuit {
+        seeds: std::cell::Cell<(u64, u64)>,
+    }
+
+    impl Circuit<Fr> for LazyCircuit {
+        type Config = FibConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+            let instance = cs.instance_column();
+
+            FibChip::configure(advice, instance, cs)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let chip = FibChip::construct(config);
+            let c = chip.run_lazy(layouter.namespace(|| "lazy"), 10, || {
+                let (a, b) = self.seeds.get();
+                (Fr::from(a), Fr::from(b))
+            })?;
+            chip.expose_public(layouter.namespace(|| "out"), &c, 0)
+        }
+    }
+
+    #[test]
+    fn seeds_from_closure() {
+        let circuit = LazyCircuit::default();
+
+        for (a, b) in [(1u64, 1), (2, 3)] {
+            circuit.seeds.set((a, b));
+            let out = compute(10, Fr::from(a), Fr::from(b));
+
+            let prover = MockProver::<Fr>::run(5, &circuit, vec![vec![out]]).unwrap();
+            assert_satisfied_verbose(&prover);
+        }
+    }
+
+    // Two chains on disjoint columns under one selector, outputs at instance rows 2 and 3
+    struct SharedSelectorCircuit;
+
+    impl Circuit<Fr> for SharedSelectorCircuit {
+        type Config = (FibConfig, FibConfig);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = cs.instance_column();
+            let selector = cs.selector();
+            let configure = |cs: &mut ConstraintSystem<Fr>| {
+                let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+                FibChip::configure_shared(advice, instance, selector, cs)
+            };
+
+            (configure(cs), configure(cs))
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            for (config, fib_size, row) in [(config.0, 10, 2), (config.1, 20, 3)] {
+                let chip = FibChip::construct(config);
+                let (_, mut b, mut c) =
+                    chip.assign_first_row(layouter.namespace(|| "first row"))?;
+                for _ in 3..fib_size {
+                    let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+                    b = c;
+                    c = new_c;
+                }
+                chip.expose_public(layouter.namespace(|| "out"), &c, row)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn chips_share_selector() {
+        let mut cs = ConstraintSystem::<Fr>::default();
+        SharedSelectorCircuit::configure(&mut cs);
+        assert_eq!(cs.num_selectors(), 1);
+
+        let one = Fr::from(1);
+        let instance = vec![one, one, Fr::from(55), Fr::from(6765)];
+        let prover = MockProver::<Fr>::run(6, &SharedSelectorCircuit, vec![instance]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let instance = vec![one, one, Fr::from(55), Fr::from(6766)];
+        let prover = MockProver::<Fr>::run(6, &SharedSelectorCircuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn equality_only_where_asked() {
+        let permutation_columns = |equality: Option<[bool; 3]>| {
+            let mut cs = ConstraintSystem::<Fr>::default();
+            let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+            let instance = cs.instance_column();
+            match equality {
+                Some(equality) => {
+                    FibChip::configure_with_equality(advice, instance, equality, &mut cs);
+                }
+                None => {
+                    FibChip::configure(advice, instance, &mut cs);
+                }
+            }
+            cs.permutation().get_columns().len()
+        };
+
+        assert_eq!(permutation_columns(None), 4);
+        assert_eq!(permutation_columns(Some([true; 3])), 4);
+        assert_eq!(permutation_columns(Some([true, true, false])), 3);
+        assert_eq!(permutation_columns(Some([false; 3])), 1);
+    }
+
+    // Mixes the last two terms with the second-phase challenge, exposing nothing new
+    #[derive(Default)]
+    struct PhasedCircuit;
+
+    impl Circuit<Fr> for PhasedCircuit {
+        type Config = FibConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+            let instance = cs.instance_column();
+
+            FibChip::configure_phased(advice, instance, cs)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let chip = FibChip::construct(config);
+            let (_, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+            for _ in 3..10 {
+                let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+                b = c;
+                c = new_c;
+            }
+            chip.assign_mix(layouter.namespace(|| "mix"), &b, &c)?;
             Ok(())
         }
     }
 
+    #[test]
+    fn phased_gate_verifies() {
+        use crate::prover::{gen_params, gen_pk, prove_fib, verify_fib};
+
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        let prover = MockProver::<Fr>::run(5, &PhasedCircuit, instances.clone()).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let params = gen_params(5);
+        let pk = gen_pk(&params, &PhasedCircuit).unwrap();
+
+        let proof = prove_fib(&params, &pk, PhasedCircuit, &instances, false).unwrap();
+        verify_fib(&params, pk.get_vk(), &instances, &proof).unwrap();
+    }
+
     #[test]
     fn main() {
         let k = 20;
@@ -209,15 +1070,397 @@ mod test {
         let b = Fr::from(1);
         // let out = Fr::from(102334155);
 
-        let circuit = MyCircuit { fib_size: 1000000 };
+        let circuit = FibCircuit {
+            fib_size: 1000000,
+            ..Default::default()
+        };
 
         // Vector for the public input column (if we had more, we'd need to add additional)
         let public_input = vec![a, b];
         let instance_columns = vec![public_input];
 
         let prover = MockProver::<Fr>::run(k, &circuit, instance_columns).unwrap();
-        prover.assert_satisfied();
+        assert_satisfied_verbose(&prover);
 
         println!("Proof generated successfully!");
     }
+
+    #[test]
+    fn optional_exposure() {
+        let one = Fr::from(1);
+        let run = |expose_output, out: u64| {
+            let circuit = FibCircuit {
+                fib_size: 10,
+                expose_output,
+                ..Default::default()
+            };
+            MockProver::<Fr>::run(4, &circuit, vec![vec![one, one, Fr::from(out)]]).unwrap()
+        };
+
+        // Exposed, the output row must hold F_10
+        assert_satisfied_verbose(&run(true, 55));
+        assert!(run(true, 56).verify().is_err());
+
+        // Internal, nothing ties the output row to the chain
+        assert_satisfied_verbose(&run(false, 56));
+    }
+
+    // Assigns a single row that breaks a + b = c, with the selector and its guard under test
+    // control
+    struct GatedRowCircuit {
+        enable: bool,
+        check_enabled: bool,
+    }
+
+    impl Circuit<Fr> for GatedRowCircuit {
+        type Config = FibConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                enable: self.enable,
+                check_enabled: self.check_enabled,
+            }
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+            let instance = cs.instance_column();
+
+            FibChip::configure(advice, instance, cs)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let chip = FibChip::construct(config.clone());
+            layouter.assign_region(
+                || "bad row",
+                |mut region| {
+                    if self.enable {
+                        chip.enable_selector(&mut region, 0)?;
+                    }
+                    for (column, value) in config.advice.iter().zip([1u64, 1, 5]) {
+                        region.assign_advice(
+                            || "x",
+                            *column,
+                            0,
+                            || Value::known(Fr::from(value)),
+                        )?;
+                    }
+                    Ok(())
+                },
+            )?;
+
+            if self.check_enabled {
+                chip.check_selector_enabled(1)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn selector_gates_constraint() {
+        // s = 0: 1 + 1 != 5 is not checked
+        let circuit = GatedRowCircuit {
+            enable: false,
+            check_enabled: false,
+        };
+        let prover = MockProver::<Fr>::run(4, &circuit, vec![vec![]]);
+        prover.unwrap().assert_satisfied();
+
+        // s = 1: the same row now violates the add gate
+        let circuit = GatedRowCircuit {
+            enable: true,
+            check_enabled: false,
+        };
+        let prover = MockProver::<Fr>::run(4, &circuit, vec![vec![]]);
+        assert!(prover.unwrap().verify().is_err());
+    }
+
+    #[test]
+    fn debug_guard_catches_inconsistent_row() {
+        let one = Fr::from(1);
+
+        // A first row witnessing c = 3 for seeds (1, 1)
+        let circuit = OverriddenFirstRowCircuit { c: Fr::from(3) };
+        let result = MockProver::<Fr>::run(4, &circuit, vec![vec![one, one]]);
+        if cfg!(debug_assertions) {
+            assert!(matches!(result, Err(Error::Synthesis)));
+        } else {
+            assert!(result.unwrap().verify().is_err());
+        }
+
+        // The honest first row passes the guard
+        let circuit = OverriddenFirstRowCircuit { c: Fr::from(2) };
+        let prover = MockProver::<Fr>::run(4, &circuit, vec![vec![one, one]]).unwrap();
+        prover.assert_satisfied();
+
+        // A stored witness with F_7 bumped off the sequence
+        let mut terms = precompute_sequence(10, one, one);
+        terms[6] += one;
+        let circuit = StoredWitnessCircuit {
+            circuit: FibCircuit {
+                fib_size: 10,
+                ..Default::default()
+            },
+            terms,
+        };
+        let result = MockProver::<Fr>::run(4, &circuit, vec![vec![one, one]]);
+        if cfg!(debug_assertions) {
+            assert!(matches!(result, Err(Error::Synthesis)));
+        } else {
+            assert!(result.unwrap().verify().is_err());
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn guard_catches_selector_never_enabled() {
+        let circuit = GatedRowCircuit {
+            enable: false,
+            check_enabled: true,
+        };
+        assert!(matches!(
+            MockProver::<Fr>::run(4, &circuit, vec![vec![]]),
+            Err(Error::Synthesis)
+        ));
+
+        // Enabled, the guard passes and the bad row is left to the gate
+        let circuit = GatedRowCircuit {
+            enable: true,
+            check_enabled: true,
+        };
+        let prover = MockProver::<Fr>::run(4, &circuit, vec![vec![]]);
+        assert!(prover.unwrap().verify().is_err());
+    }
+
+    // A chain under split selectors with one extra first-row gate requiring equal seeds
+    #[derive(Default)]
+    struct SplitSeedCircuit;
+
+    impl Circuit<Fr> for SplitSeedCircuit {
+        type Config = FibConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+            let instance = cs.instance_column();
+            let config = FibChip::configure_split(advice, instance, cs);
+
+            let first = config.split.as_ref().unwrap().first_row_selector;
+            cs.create_gate("equal seeds", |cells| {
+                let s = cells.query_selector(first);
+                let a = cells.query_advice(advice[0], Rotation::cur());
+                let b = cells.query_advice(advice[1], Rotation::cur());
+
+                vec![s * (a - b)]
+            });
+            config
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let chip = FibChip::construct(config);
+            let (_, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+            for _ in 3..8 {
+                let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+                b = c;
+                c = new_c;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn first_row_selector_applies_once() {
+        // No later row has a == b, so passing means the seed gate stayed on the first row
+        let one = Fr::from(1);
+        let prover = MockProver::run(4, &SplitSeedCircuit, vec![vec![one, one]]).unwrap();
+        prover.assert_satisfied();
+
+        let prover = MockProver::run(4, &SplitSeedCircuit, vec![vec![one, Fr::from(2)]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert_eq!(failures.len(), 1, "{failures:?}");
+
+        // The add gate still covers every row under one selector or the other
+        let mut cs = ConstraintSystem::<Fr>::default();
+        SplitSeedCircuit::configure(&mut cs);
+        assert_eq!(cs.num_selectors(), 2);
+    }
+
+    // FibCircuit with a + b - c installed through `configure_custom`
+    struct CustomAddCircuit;
+
+    impl Circuit<Fr> for CustomAddCircuit {
+        type Config = FibConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+            let instance = cs.instance_column();
+
+            FibChip::configure_custom(advice, instance, cs, |cells| {
+                let a = cells.query_advice(advice[0], Rotation::cur());
+                let b = cells.query_advice(advice[1], Rotation::cur());
+                let c = cells.query_advice(advice[2], Rotation::cur());
+
+                vec![a + b - c]
+            })
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let chip = FibChip::construct(config);
+            let (_, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+            for _ in 3..10 {
+                let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+                b = c;
+                c = new_c;
+            }
+            chip.expose_public(layouter.namespace(|| "out"), &c, 2)
+        }
+    }
+
+    #[test]
+    fn custom_gate_matches_builtin() {
+        let polys = |cs: &ConstraintSystem<Fr>| format!("{:?}", cs.gates()[0].polynomials());
+        let mut builtin = ConstraintSystem::<Fr>::default();
+        <FibCircuit as Circuit<Fr>>::configure(&mut builtin);
+        let mut custom = ConstraintSystem::<Fr>::default();
+        CustomAddCircuit::configure(&mut custom);
+        assert_eq!(custom.gates().len(), 1);
+        assert_eq!(polys(&custom), polys(&builtin));
+
+        let one = Fr::from(1);
+        let prover = MockProver::run(4, &CustomAddCircuit, vec![vec![one, one, Fr::from(55)]]);
+        assert_satisfied_verbose(&prover.unwrap());
+        let prover = MockProver::run(4, &CustomAddCircuit, vec![vec![one, one, Fr::from(56)]]);
+        assert!(prover.unwrap().verify().is_err());
+    }
+
+    // FibCircuit's chain with one `assign_row` witness replaced
+    struct OverriddenRowCircuit {
+        step: usize,
+        c: Fr,
+    }
+
+    impl Circuit<Fr> for OverriddenRowCircuit {
+        type Config = FibConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                step: self.step,
+                c: self.c,
+            }
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+            let instance = cs.instance_column();
+
+            FibChip::configure(advice, instance, cs)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let chip = FibChip::construct(config);
+            chip.override_c(self.step, self.c);
+
+            let (_, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+            for _ in 3..10 {
+                let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+                b = c;
+                c = new_c;
+            }
+            Ok(())
+        }
+    }
+
+    // FibCircuit's first row with its `c` replaced
+    struct OverriddenFirstRowCircuit {
+        c: Fr,
+    }
+
+    impl Circuit<Fr> for OverriddenFirstRowCircuit {
+        type Config = FibConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self { c: self.c }
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+            let instance = cs.instance_column();
+
+            FibChip::configure(advice, instance, cs)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let chip = FibChip::construct(config);
+            chip.override_first_c(self.c);
+
+            chip.assign_first_row(layouter.namespace(|| "first row"))?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn overridden_c_fails_at_its_row() {
+        use halo2_base::halo2_proofs::dev::{FailureLocation, VerifyFailure};
+
+        // The fourth `assign_row` computes F_7 = 13 in region 4, after the first row's region 0
+        let circuit = OverriddenRowCircuit {
+            step: 3,
+            c: Fr::from(14),
+        };
+        let prover = MockProver::run(4, &circuit, vec![vec![Fr::from(1), Fr::from(1)]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+
+        // Later rows build on 14 consistently, so only the overridden row breaks the gate
+        assert_eq!(failures.len(), 1, "{failures:?}");
+        match &failures[0] {
+            VerifyFailure::ConstraintNotSatisfied { location, .. } => assert_eq!(
+                *location,
+                FailureLocation::InRegion {
+                    region: (4, "next_row").into(),
+                    offset: 0,
+                }
+            ),
+            failure => panic!("expected the add gate to fail, got {failure}"),
+        }
+
+        // Overriding with the honest value is accepted
+        let circuit = OverriddenRowCircuit {
+            step: 3,
+            c: Fr::from(13),
+        };
+        let prover = MockProver::run(4, &circuit, vec![vec![Fr::from(1), Fr::from(1)]]).unwrap();
+        prover.assert_satisfied();
+    }
 }
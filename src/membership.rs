@@ -0,0 +1,421 @@
+use crate::layout::{plan_rows, LayoutKind};
+use halo2_base::halo2_proofs::{
+    circuit::*,
+    halo2curves::{bn256::Fr, FieldExt},
+    plonk::*,
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b, x, is_member]
+const X_ROW: usize = 2;
+const IS_MEMBER_ROW: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct MembershipConfig {
+    pub advice: [Column<Advice>; 3],
+    pub x: Column<Advice>,
+    pub inv: Column<Advice>,
+    pub eq: Column<Advice>,
+    pub acc: Column<Advice>,
+    pub selector: Selector,
+    pub first: Selector,
+    pub step: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// Proves whether a public `x` is one of F_2..=F_n without revealing which one.
+///
+/// Every row carries `eq = 1` exactly when `b == x` (an is-zero gadget over `b - x`), and
+/// `acc` sums those flags down the region. The final `acc` is exposed, so a verifier checks
+/// it against 1 to accept membership or 0 to accept the denial. F_1 is skipped because with
+/// the standard seeds it repeats F_2 and would count twice.
+pub struct MembershipChip<F: FieldExt> {
+    config: MembershipConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> MembershipChip<F> {
+    pub fn construct(config: MembershipConfig) -> MembershipChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_membership(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> MembershipConfig {
+        let [col_a, col_b, col_c] = advice;
+        let x = cs.advice_column();
+        let inv = cs.advice_column();
+        let eq = cs.advice_column();
+        let acc = cs.advice_column();
+        let selector = cs.selector();
+        let first = cs.selector();
+        let step = cs.selector();
+
+        // Seeds and x come in from the instance, the count goes out to it
+        cs.enable_equality(col_a);
+        cs.enable_equality(col_b);
+        cs.enable_equality(x);
+        cs.enable_equality(acc);
+        cs.enable_equality(instance);
+
+        cs.create_gate("membership", |cells| {
+            let s = cells.query_selector(selector);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let c = cells.query_advice(col_c, Rotation::cur());
+            let x = cells.query_advice(x, Rotation::cur());
+            let inv = cells.query_advice(inv, Rotation::cur());
+            let eq = cells.query_advice(eq, Rotation::cur());
+
+            // eq is forced to 1 when b == x and to 0 otherwise, so it needs no boolean check
+            let diff = b.clone() - x;
+            vec![
+                s.clone() * (a + b - c),
+                s.clone() * diff.clone() * eq.clone(),
+                s * (Expression::Constant(F::one()) - diff * inv - eq),
+            ]
+        });
+
+        cs.create_gate("membership first", |cells| {
+            let s = cells.query_selector(first);
+            let eq = cells.query_advice(eq, Rotation::cur());
+            let acc = cells.query_advice(acc, Rotation::cur());
+
+            vec![s * (acc - eq)]
+        });
+
+        // The whole sequence lives in one region, so rows chain by rotation instead of copies
+        cs.create_gate("membership step", |cells| {
+            let s = cells.query_selector(step);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let b_prev = cells.query_advice(col_b, Rotation::prev());
+            let c_prev = cells.query_advice(col_c, Rotation::prev());
+            let x_cur = cells.query_advice(x, Rotation::cur());
+            let x_prev = cells.query_advice(x, Rotation::prev());
+            let eq = cells.query_advice(eq, Rotation::cur());
+            let acc = cells.query_advice(acc, Rotation::cur());
+            let acc_prev = cells.query_advice(acc, Rotation::prev());
+
+            vec![
+                s.clone() * (a - b_prev),
+                s.clone() * (b - c_prev),
+                s.clone() * (x_cur - x_prev),
+                s * (acc - acc_prev - eq),
+            ]
+        });
+
+        MembershipConfig {
+            advice,
+            x,
+            inv,
+            eq,
+            acc,
+            selector,
+            first,
+            step,
+            instance,
+        }
+    }
+
+    /// Assigns the sequence up to F_{fib_size + 1} and returns the match count cell
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        fib_size: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = &self.config;
+
+        layouter.assign_region(
+            || "membership",
+            |mut region| {
+                let mut a = region
+                    .assign_advice_from_instance(|| "a", config.instance, 0, config.advice[0], 0)?
+                    .value()
+                    .copied();
+                let mut b = region
+                    .assign_advice_from_instance(|| "b", config.instance, 1, config.advice[1], 0)?
+                    .value()
+                    .copied();
+                let x = region
+                    .assign_advice_from_instance(|| "x", config.instance, X_ROW, config.x, 0)?
+                    .value()
+                    .copied();
+
+                let mut acc = Value::known(F::zero());
+                let mut acc_cell = None;
+
+                for row in 0..fib_size.saturating_sub(1).max(1) {
+                    config.selector.enable(&mut region, row)?;
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else {
+                        config.step.enable(&mut region, row)?;
+                        region.assign_advice(|| "a", config.advice[0], row, || a)?;
+                        region.assign_advice(|| "b", config.advice[1], row, || b)?;
+                        region.assign_advice(|| "x", config.x, row, || x)?;
+                    }
+
+                    let c = a + b;
+                    region.assign_advice(|| "c", config.advice[2], row, || c)?;
+
+                    let diff = b - x;
+                    let inv = diff.map(|diff| diff.invert().unwrap_or(F::zero()));
+                    let eq = diff.map(|diff| {
+                        if diff == F::zero() {
+                            F::one()
+                        } else {
+                            F::zero()
+                        }
+                    });
+                    region.assign_advice(|| "inv", config.inv, row, || inv)?;
+                    region.assign_advice(|| "eq", config.eq, row, || eq)?;
+
+                    acc = acc + eq;
+                    acc_cell = Some(region.assign_advice(|| "acc", config.acc, row, || acc)?);
+
+                    a = b;
+                    b = c;
+                }
+
+                acc_cell.ok_or(Error::Synthesis)
+            },
+        )
+    }
+
+    pub fn expose_is_member(
+        &self,
+        mut layouter: impl Layouter<F>,
+        acc: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(acc.cell(), self.config.instance, IS_MEMBER_ROW)
+    }
+
+    /// Membership with the count pinned to one in-circuit rather than exposed: proving
+    /// knowledge of an index i with F_i = x while the matching row stays private.
+    pub fn configure_index_knowledge(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> MembershipConfig {
+        let constant = cs.fixed_column();
+        cs.enable_constant(constant);
+
+        Self::configure_membership(advice, instance, cs)
+    }
+
+    /// Requires exactly one row to have matched; needs `configure_index_knowledge`
+    pub fn constrain_single_match(
+        &self,
+        mut layouter: impl Layouter<F>,
+        acc: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "single match",
+            |mut region| {
+                let one =
+                    region.assign_advice_from_constant(|| "one", self.config.acc, 0, F::one())?;
+                region.constrain_equal(acc.cell(), one.cell())
+            },
+        )
+    }
+}
+
+/// Off-circuit search for the index i in 2..=fib_size with F_i = x, the witness a prover
+/// holds for `IndexKnowledgeCircuit`
+pub fn recover_index<F: FieldExt>(x: F, a: F, b: F, fib_size: usize) -> Option<usize> {
+    let (mut prev, mut cur) = (a, b);
+    for i in 2..=fib_size {
+        if cur == x {
+            return Some(i);
+        }
+        let next = prev + cur;
+        prev = cur;
+        cur = next;
+    }
+    None
+}
+
+/// Smallest `fib_size` for which `MembershipCircuit` with the standard seeds (1, 1) reaches
+/// x, i.e. the first i with F_i >= x.
+///
+/// Pass it to `min_k_for_membership` to size the circuit to the target. Never below 2, since
+/// the circuit starts its search at F_2.
+pub fn min_size_for_value(x: u64) -> usize {
+    let x = u128::from(x);
+    let (mut prev, mut cur, mut i) = (1u128, 1u128, 2);
+    while cur < x {
+        (prev, cur) = (cur, prev + cur);
+        i += 1;
+    }
+    i
+}
+
+/// Smallest `k` whose usable rows fit `MembershipCircuit` for `fib_size`.
+///
+/// The search lays out one row per term from F_2, one more than `FibCircuit`'s n - 2, so
+/// `min_k_for` can come up a power of two short.
+pub fn min_k_for_membership(fib_size: usize) -> u32 {
+    let mut cs = ConstraintSystem::<Fr>::default();
+    <MembershipCircuit as Circuit<Fr>>::configure(&mut cs);
+    let rows = plan_rows(fib_size, LayoutKind::Membership)
+        .saturating_add(cs.blinding_factors() + 1)
+        .max(cs.minimum_rows());
+
+    rows.checked_next_power_of_two()
+        .map_or(usize::BITS, usize::trailing_zeros)
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MembershipCircuit {
+    pub fib_size: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for MembershipCircuit {
+    type Config = MembershipConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        MembershipChip::configure_membership(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = MembershipChip::construct(config);
+
+        let acc = chip.assign(layouter.namespace(|| "membership"), self.fib_size)?;
+        chip.expose_is_member(layouter.namespace(|| "is member"), &acc)
+    }
+}
+
+/// Instance: [seed_a, seed_b, x]. `fib_size` only bounds the search; the index is private.
+#[derive(Clone, Debug, Default)]
+pub struct IndexKnowledgeCircuit {
+    pub fib_size: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for IndexKnowledgeCircuit {
+    type Config = MembershipConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        MembershipChip::configure_index_knowledge(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = MembershipChip::construct(config);
+
+        let acc = chip.assign(layouter.namespace(|| "sequence"), self.fib_size)?;
+        chip.constrain_single_match(layouter.namespace(|| "single match"), &acc)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::{MockProver, VerifyFailure};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    fn run(x: u64, is_member: u64) -> Result<(), Vec<VerifyFailure>> {
+        let circuit = MembershipCircuit { fib_size: 10 };
+        let instance = vec![Fr::from(1), Fr::from(1), Fr::from(x), Fr::from(is_member)];
+
+        MockProver::<Fr>::run(5, &circuit, vec![instance])
+            .unwrap()
+            .verify()
+    }
+
+    #[test]
+    fn membership() {
+        // 55 = F_10
+        assert!(run(55, 1).is_ok());
+        assert!(run(55, 0).is_err());
+
+        // 56 sits between F_10 and F_11, so only the denial verifies
+        assert!(run(56, 1).is_err());
+        assert!(run(56, 0).is_ok());
+    }
+
+    #[test]
+    fn size_for_value() {
+        assert_eq!(min_size_for_value(55), 10);
+        assert_eq!(min_size_for_value(56), 11);
+        assert_eq!(min_size_for_value(1), 2);
+        assert_eq!(min_size_for_value(u64::MAX), 94);
+
+        // Just long enough: 55 is found, and one step shorter it is not
+        let fib_size = min_size_for_value(55);
+        let k = min_k_for_membership(fib_size);
+        let instance = vec![Fr::from(1), Fr::from(1), Fr::from(55), Fr::from(1)];
+        let circuit = MembershipCircuit { fib_size };
+        let prover = MockProver::<Fr>::run(k, &circuit, vec![instance.clone()]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let circuit = MembershipCircuit {
+            fib_size: fib_size - 1,
+        };
+        let prover = MockProver::<Fr>::run(k, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // Just past F_59 takes 60 terms: 59 rows and 6 for blinding, one over the 64 that
+        // `FibCircuit` needs at the same size
+        let x = 956_722_026_041 + 1;
+        let fib_size = min_size_for_value(x);
+        assert_eq!(fib_size, 60);
+        assert_eq!(crate::prover::min_k_for(fib_size), 6);
+        assert_eq!(min_k_for_membership(fib_size), 7);
+
+        let instance = vec![Fr::from(1), Fr::from(1), Fr::from(x), Fr::from(0)];
+        let circuit = MembershipCircuit { fib_size };
+        assert!(matches!(
+            MockProver::<Fr>::run(6, &circuit, vec![instance.clone()]),
+            Err(Error::NotEnoughRowsAvailable { current_k: 6 })
+        ));
+        let prover = MockProver::<Fr>::run(7, &circuit, vec![instance]).unwrap();
+        assert_satisfied_verbose(&prover);
+    }
+
+    #[test]
+    fn index_knowledge() {
+        let one = Fr::from(1);
+        assert_eq!(recover_index(Fr::from(89), one, one, 16), Some(11));
+
+        // The instance carries 89 but not 11
+        let circuit = IndexKnowledgeCircuit { fib_size: 16 };
+        let instance = vec![one, one, Fr::from(89)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let instance = vec![one, one, Fr::from(90)];
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use crate::digest::TraceDigestConfig;
+use crate::private_seeds::{poseidon_hash, PoseidonCommit};
+use crate::FibChip;
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*};
+
+// Instance layout: [root]
+const ROOT_ROW: usize = 0;
+
+// Each leaf is hashed as H(LEAF_TAG, leaf) before it enters the tree, and a node left over on
+// an odd level as H(node, PAD_TAG). Internal nodes are H(left, right) of hash outputs, so a
+// leaf can never pass for an internal node, nor a node for one on another level.
+const LEAF_TAG: u64 = 0;
+const PAD_TAG: u64 = 1;
+
+/// Every level of the Merkle tree over `leaves`, from the leaf hashes up to the root, hashing
+/// with the two-to-one `poseidon_hash`. Empty for no leaves.
+pub fn merkle_levels<F: FieldExt>(leaves: &[F]) -> Vec<Vec<F>> {
+    if leaves.is_empty() {
+        return vec![];
+    }
+
+    let leaf_tag = F::from(LEAF_TAG);
+    let mut levels = vec![leaves
+        .iter()
+        .map(|leaf| poseidon_hash(leaf_tag, *leaf))
+        .collect::<Vec<_>>()];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => poseidon_hash(*left, *right),
+                [node] => poseidon_hash(*node, F::from(PAD_TAG)),
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+pub fn merkle_root<F: FieldExt>(leaves: &[F]) -> Option<F> {
+    merkle_levels(leaves).pop().map(|root| root[0])
+}
+
+/// Hashes `leaves` up to the root in-circuit, tagging and pairing nodes the same way as
+/// `merkle_levels`
+pub fn merkle_root_cells<F: FieldExt>(
+    config: &TraceDigestConfig<F>,
+    mut layouter: impl Layouter<F>,
+    leaves: &[AssignedCell<F, F>],
+) -> Result<AssignedCell<F, F>, Error> {
+    let (leaf_tag, pad_tag) = layouter.assign_region(
+        || "merkle tags",
+        |mut region| {
+            let column = config.fib.advice[0];
+            let leaf =
+                region.assign_advice_from_constant(|| "leaf tag", column, 0, F::from(LEAF_TAG))?;
+            let pad =
+                region.assign_advice_from_constant(|| "pad tag", column, 1, F::from(PAD_TAG))?;
+            Ok((leaf, pad))
+        },
+    )?;
+    let poseidon = &config.poseidon;
+
+    let mut level = vec![];
+    for leaf in leaves {
+        let ns = layouter.namespace(|| "leaf");
+        level.push(PoseidonCommit::hash_cells(
+            poseidon.clone(),
+            ns,
+            &leaf_tag,
+            leaf,
+        )?);
+    }
+    while level.len() > 1 {
+        let mut next = vec![];
+        for pair in level.chunks(2) {
+            next.push(match pair {
+                [left, right] => {
+                    let ns = layouter.namespace(|| "node");
+                    PoseidonCommit::hash_cells(poseidon.clone(), ns, left, right)?
+                }
+                [node] => {
+                    let ns = layouter.namespace(|| "pad");
+                    PoseidonCommit::hash_cells(poseidon.clone(), ns, node, &pad_tag)?
+                }
+                _ => unreachable!(),
+            });
+        }
+        level = next;
+    }
+    level.pop().ok_or(Error::Synthesis)
+}
+
+/// Fibonacci from private seeds whose only public value is `merkle_root` over every term
+/// F_1..=F_{fib_size}. The tree can be rebuilt off-circuit with `merkle_levels` to link a single
+/// term to the root with an ordinary inclusion path.
+#[derive(Clone, Debug, Default)]
+pub struct MerkleTraceCircuit<F> {
+    pub seeds: (Value<F>, Value<F>),
+    pub fib_size: usize,
+}
+
+impl<F: FieldExt> MerkleTraceCircuit<F> {
+    pub fn new(a: F, b: F, fib_size: usize) -> Self {
+        Self {
+            seeds: (Value::known(a), Value::known(b)),
+            fib_size,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MerkleTraceCircuit<F> {
+    type Config = TraceDigestConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            seeds: (Value::unknown(), Value::unknown()),
+            fib_size: self.fib_size,
+        }
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        TraceDigestConfig::configure(cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        let (a, b) = layouter.assign_region(
+            || "seeds",
+            |mut region| {
+                let a = region.assign_advice(|| "a", config.fib.advice[0], 0, || self.seeds.0)?;
+                let b = region.assign_advice(|| "b", config.fib.advice[1], 0, || self.seeds.1)?;
+                Ok((a, b))
+            },
+        )?;
+
+        let chip = FibChip::construct(config.fib.clone());
+        let (a, mut b, mut c) =
+            chip.assign_first_row_from_cells(layouter.namespace(|| "first row"), &a, &b)?;
+        let mut terms = vec![a, b.clone(), c.clone()];
+        for _ in 3..self.fib_size {
+            let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            terms.push(new_c.clone());
+            b = c;
+            c = new_c;
+        }
+
+        let root = merkle_root_cells(&config, layouter.namespace(|| "merkle"), &terms)?;
+        chip.expose_public(layouter.namespace(|| "root"), &root, ROOT_ROW)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::precompute_sequence;
+    use crate::testing::{assert_satisfied_verbose, StubbedTraceCircuit, TraceCommitment};
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    impl TraceCommitment for MerkleTraceCircuit<Fr> {
+        const ROW: usize = ROOT_ROW;
+
+        fn commit(
+            config: &TraceDigestConfig<Fr>,
+            layouter: impl Layouter<Fr>,
+            terms: &[AssignedCell<Fr, Fr>],
+        ) -> Result<AssignedCell<Fr, Fr>, Error> {
+            merkle_root_cells(config, layouter, terms)
+        }
+    }
+
+    #[test]
+    fn root_covers_every_step() {
+        let (a, b) = (Fr::from(1), Fr::from(1));
+        let terms = precompute_sequence(10, a, b);
+        let levels = merkle_levels(&terms);
+        // 10 -> 5 -> 3 -> 2 -> 1
+        assert_eq!(
+            levels.iter().map(Vec::len).collect::<Vec<_>>(),
+            [10, 5, 3, 2, 1]
+        );
+        let root = merkle_root(&terms).unwrap();
+
+        // A level of internal nodes passed off as leaves does not rebuild the same root
+        assert_ne!(merkle_root(&levels[1]), Some(root));
+
+        let circuit = MerkleTraceCircuit::new(a, b, 10);
+        let prover = MockProver::run(11, &circuit, vec![vec![root]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        // One intermediate step swapped out
+        let mut tampered = terms.clone();
+        tampered[5] += Fr::from(1);
+        let tampered_root = merkle_root(&tampered).unwrap();
+        assert_ne!(tampered_root, root);
+
+        let stubbed = StubbedTraceCircuit::<MerkleTraceCircuit<Fr>>::new(tampered);
+        let prover = MockProver::run(11, &stubbed, vec![vec![root]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct ModStepConfig {
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    /// 1 where a + b wrapped past the modulus
+    pub carry: Column<Advice>,
+    pub modulus: Column<Fixed>,
+    pub table: TableColumn,
+    pub range: Selector,
+    pub step: Selector,
+}
+
+/// One Fibonacci step reduced mod m: (a, b) to (b, a + b mod m).
+///
+/// Row i holds the pair and the next row holds its successor. The sum is reduced with a
+/// boolean carry and checked to be a residue by a lookup into 0..m, which is exact because
+/// both inputs are already below m. The modulus sits in a fixed column, so it is part of the
+/// verifying key, and the table has to be loaded with the same m.
+pub struct ModStepChip<F: FieldExt> {
+    config: ModStepConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ModStepChip<F> {
+    pub fn construct(config: ModStepConfig) -> ModStepChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_mod_step(cs: &mut ConstraintSystem<F>) -> ModStepConfig {
+        let a = cs.advice_column();
+        let b = cs.advice_column();
+        let carry = cs.advice_column();
+        let modulus = cs.fixed_column();
+        let table = cs.lookup_table_column();
+        let range = cs.complex_selector();
+        let step = cs.selector();
+
+        // A disabled row looks up 0, which is always in the table
+        cs.lookup("residue", |cells| {
+            let s = cells.query_selector(range);
+            let a = cells.query_advice(a, Rotation::cur());
+            let b = cells.query_advice(b, Rotation::cur());
+
+            vec![(s.clone() * a, table), (s * b, table)]
+        });
+
+        cs.create_gate("mod step", |cells| {
+            let s = cells.query_selector(step);
+            let a = cells.query_advice(a, Rotation::cur());
+            let b = cells.query_advice(b, Rotation::cur());
+            let carry = cells.query_advice(carry, Rotation::cur());
+            let m = cells.query_fixed(modulus, Rotation::cur());
+            let a_next = cells.query_advice(a, Rotation::next());
+            let b_next = cells.query_advice(b, Rotation::next());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * carry.clone() * (one - carry.clone()),
+                s.clone() * (a_next - b.clone()),
+                s * (a + b - b_next - carry * m),
+            ]
+        });
+
+        ModStepConfig {
+            a,
+            b,
+            carry,
+            modulus,
+            table,
+            range,
+            step,
+        }
+    }
+
+    pub fn load_table(&self, mut layouter: impl Layouter<F>, modulus: u64) -> Result<(), Error> {
+        layouter.assign_table(
+            || "residues",
+            |mut table| {
+                for r in 0..modulus {
+                    table.assign_cell(
+                        || "residue",
+                        self.config.table,
+                        r as usize,
+                        || Value::known(F::from(r)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Steps from the pair (a, b) held at `row`: fills in the carry and returns the pair for
+    /// `row + 1`, which the caller assigns. The last row of a run still needs `range` enabled.
+    pub fn assign_step(
+        &self,
+        region: &mut Region<'_, F>,
+        row: usize,
+        modulus: u64,
+        a: Value<F>,
+        b: Value<F>,
+    ) -> Result<(Value<F>, Value<F>), Error> {
+        let config = &self.config;
+        let m = F::from(modulus);
+
+        config.range.enable(region, row)?;
+        config.step.enable(region, row)?;
+        region.assign_fixed(|| "modulus", config.modulus, row, || Value::known(m))?;
+
+        // Both inputs are residues, so a + b wraps at most once
+        let wrapped = a
+            .zip(b)
+            .map(|(a, b)| u128::from(modulus) <= a.get_lower_128() + b.get_lower_128());
+        let carry = wrapped.map(|wrapped| F::from(u64::from(wrapped)));
+        region.assign_advice(|| "carry", config.carry, row, || carry)?;
+
+        Ok((b, a + b - carry * Value::known(m)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[derive(Clone, Default)]
+    struct StepsCircuit {
+        seeds: (u64, u64),
+        steps: usize,
+    }
+
+    impl Circuit<Fr> for StepsCircuit {
+        type Config = ModStepConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            ModStepChip::configure_mod_step(cs)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let chip = ModStepChip::construct(config.clone());
+            chip.load_table(layouter.namespace(|| "residue table"), 10)?;
+
+            layouter.assign_region(
+                || "steps",
+                |mut region| {
+                    let mut a = Value::known(Fr::from(self.seeds.0));
+                    let mut b = Value::known(Fr::from(self.seeds.1));
+                    region.assign_advice(|| "a", config.a, 0, || a)?;
+                    region.assign_advice(|| "b", config.b, 0, || b)?;
+
+                    for row in 0..self.steps {
+                        (a, b) = chip.assign_step(&mut region, row, 10, a, b)?;
+                        region.assign_advice(|| "a", config.a, row + 1, || a)?;
+                        region.assign_advice(|| "b", config.b, row + 1, || b)?;
+                    }
+                    config.range.enable(&mut region, self.steps)
+                },
+            )
+        }
+    }
+
+    fn run(seeds: (u64, u64)) -> MockProver<Fr> {
+        let circuit = StepsCircuit { seeds, steps: 5 };
+        MockProver::run(5, &circuit, vec![]).unwrap()
+    }
+
+    #[test]
+    fn steps_stay_residues() {
+        // 7, 8, 5, 3, 8, 1, 9 mod 10, wrapping on three of the five steps
+        assert_satisfied_verbose(&run((7, 8)));
+        assert_satisfied_verbose(&run((0, 0)));
+
+        // A seed outside 0..10 fails the lookup even though every step is consistent
+        assert!(run((12, 1)).verify().is_err());
+    }
+}
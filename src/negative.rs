@@ -0,0 +1,181 @@
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [F_1, F_2, F_{-n}], matching `FibCircuit` with `expose_output`
+const OUT_ROW: usize = 2;
+
+/// Off-circuit F_{-n} for seeds F_1 = a, F_2 = b, running F_{k-1} = F_{k+1} - F_k backwards.
+/// With the standard seeds this is (-1)^{n+1} F_n, negatives being `p - |F_{-n}|` in the field.
+pub fn compute_negative<F: FieldExt>(n: usize, a: F, b: F) -> F {
+    let (mut hi, mut lo) = (b, a);
+    for _ in 0..=n {
+        let next = hi - lo;
+        hi = lo;
+        lo = next;
+    }
+    lo
+}
+
+#[derive(Debug, Clone)]
+pub struct NegativeConfig {
+    /// The state (F_{k+1}, F_k), one row per state, k falling by one each row
+    pub state: [Column<Advice>; 2],
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// Walks the sequence below F_1 with the reverse recurrence. Nothing special is needed for
+/// the alternating sign: subtraction in the field already yields `p - x` for a negative `-x`.
+pub struct NegativeFibChip<F: FieldExt> {
+    config: NegativeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> NegativeFibChip<F> {
+    pub fn construct(config: NegativeConfig) -> NegativeFibChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_negative(
+        state: [Column<Advice>; 2],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> NegativeConfig {
+        let [col_hi, col_lo] = state;
+        let selector = cs.selector();
+
+        cs.enable_equality(col_hi);
+        cs.enable_equality(col_lo);
+        cs.enable_equality(instance);
+
+        // (F_{k+1}, F_k) -> (F_k, F_{k+1} - F_k)
+        cs.create_gate("reverse step", |cells| {
+            let s = cells.query_selector(selector);
+            let hi = cells.query_advice(col_hi, Rotation::cur());
+            let lo = cells.query_advice(col_lo, Rotation::cur());
+            let hi_next = cells.query_advice(col_hi, Rotation::next());
+            let lo_next = cells.query_advice(col_lo, Rotation::next());
+
+            vec![s.clone() * (hi_next - lo.clone()), s * (lo_next - hi + lo)]
+        });
+
+        NegativeConfig {
+            state,
+            selector,
+            instance,
+        }
+    }
+
+    /// Steps from (F_2, F_1), read from the instance, down to (F_{1-n}, F_{-n}) and returns the
+    /// F_{-n} cell
+    pub fn assign_negative(
+        &self,
+        mut layouter: impl Layouter<F>,
+        n: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let [col_hi, col_lo] = self.config.state;
+
+        layouter.assign_region(
+            || "negative",
+            |mut region| {
+                let mut hi = region.assign_advice_from_instance(
+                    || "F_2",
+                    self.config.instance,
+                    1,
+                    col_hi,
+                    0,
+                )?;
+                let mut lo = region.assign_advice_from_instance(
+                    || "F_1",
+                    self.config.instance,
+                    0,
+                    col_lo,
+                    0,
+                )?;
+
+                // F_0 is one step below F_1, F_{-n} is n + 1
+                for row in 1..=n + 1 {
+                    self.config.selector.enable(&mut region, row - 1)?;
+
+                    let next_lo = hi.value().copied() - lo.value().copied();
+                    let next_hi = lo.value().copied();
+                    hi = region.assign_advice(|| "hi", col_hi, row, || next_hi)?;
+                    lo = region.assign_advice(|| "lo", col_lo, row, || next_lo)?;
+                }
+
+                Ok(lo)
+            },
+        )
+    }
+
+    pub fn expose_negative(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, OUT_ROW)
+    }
+}
+
+/// Proves F_{-n} for the seeds in the instance
+#[derive(Clone, Debug, Default)]
+pub struct NegativeFibCircuit {
+    pub n: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for NegativeFibCircuit {
+    type Config = NegativeConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let state = [cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        NegativeFibChip::configure_negative(state, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = NegativeFibChip::construct(config);
+
+        let out = chip.assign_negative(layouter.namespace(|| "negative"), self.n)?;
+        chip.expose_negative(layouter.namespace(|| "out"), &out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::compute;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn f_minus_six() {
+        let one = Fr::from(1);
+
+        // F_{-6} = (-1)^7 F_6 = -8
+        let f_minus_6 = compute_negative(6, one, one);
+        assert_eq!(f_minus_6, -Fr::from(8));
+        assert_eq!(f_minus_6, -compute(6, one, one));
+        assert_eq!(compute_negative(5, one, one), Fr::from(5));
+
+        let circuit = NegativeFibCircuit { n: 6 };
+        let prover = MockProver::<Fr>::run(4, &circuit, vec![vec![one, one, f_minus_6]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let prover = MockProver::<Fr>::run(4, &circuit, vec![vec![one, one, Fr::from(8)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,339 @@
+use crate::circuit::max_unwrapped_index;
+use crate::digits::{DigitsChip, DigitsConfig};
+use crate::export::to_biguint;
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b, x], with the seeds held to (1, 1)
+const X_ROW: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct NonMembershipConfig {
+    pub advice: [Column<Advice>; 3],
+    pub digits: DigitsConfig,
+    /// 1 on the row whose (a, b) brackets x, 0 elsewhere
+    pub hit: Column<Advice>,
+    /// Running sums of `hit * a`, `hit * b` and `hit` down the region
+    pub lo: Column<Advice>,
+    pub hi: Column<Advice>,
+    pub count: Column<Advice>,
+    pub x: Column<Advice>,
+    /// `x - lo - 1` and `hi - x - 1` on the last row
+    pub gaps: [Column<Advice>; 2],
+    pub selector: Selector,
+    pub first: Selector,
+    pub step: Selector,
+    pub last: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// Proves a public `x` is none of F_1..=F_n by showing F_i < x < F_{i+1} for a private i.
+///
+/// Bracketing only means anything while the sequence increases, so the seeds are pinned to
+/// the standard (1, 1) and n is capped at `max_unwrapped_index`, past which the terms wrap
+/// the modulus and stop being ordered.
+///
+/// Row j holds the pair (F_{j+1}, F_{j+2}) and a boolean `hit`. The sums of `hit`, `hit * a`
+/// and `hit * b` run down the region, so on the last row `count = 1` pins exactly one pair and
+/// `lo`, `hi` are that pair, without a copy revealing which row it was. Both gaps
+/// `x - lo - 1` and `hi - x - 1` are range-checked with `DigitsChip`; a wrong pair, or an `x`
+/// equal to either end, wraps one gap past `num_digits` digits. As with `CeilingChip` that
+/// holds while 10^num_digits stays far below the modulus.
+pub struct NonMembershipChip<F: FieldExt> {
+    config: NonMembershipConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> NonMembershipChip<F> {
+    pub fn construct(config: NonMembershipConfig) -> NonMembershipChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_non_membership(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> NonMembershipConfig {
+        let [col_a, col_b, col_c] = advice;
+        let digits = DigitsChip::configure_digits(cs);
+        let hit = cs.advice_column();
+        let lo = cs.advice_column();
+        let hi = cs.advice_column();
+        let count = cs.advice_column();
+        let x = cs.advice_column();
+        let gaps = [cs.advice_column(), cs.advice_column()];
+        let selector = cs.selector();
+        let first = cs.selector();
+        let step = cs.selector();
+        let last = cs.selector();
+
+        // Seeds and x come in from the instance, the gaps go out to the digit check
+        cs.enable_equality(col_a);
+        cs.enable_equality(col_b);
+        cs.enable_equality(x);
+        cs.enable_equality(gaps[0]);
+        cs.enable_equality(gaps[1]);
+        cs.enable_equality(instance);
+
+        cs.create_gate("non-membership", |cells| {
+            let s = cells.query_selector(selector);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let c = cells.query_advice(col_c, Rotation::cur());
+            let hit = cells.query_advice(hit, Rotation::cur());
+
+            vec![
+                s.clone() * (a + b - c),
+                s * hit.clone() * (Expression::Constant(F::one()) - hit),
+            ]
+        });
+
+        cs.create_gate("non-membership first", |cells| {
+            let s = cells.query_selector(first);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let hit = cells.query_advice(hit, Rotation::cur());
+            let lo = cells.query_advice(lo, Rotation::cur());
+            let hi = cells.query_advice(hi, Rotation::cur());
+            let count = cells.query_advice(count, Rotation::cur());
+
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * (a.clone() - one.clone()),
+                s.clone() * (b.clone() - one),
+                s.clone() * (lo - hit.clone() * a),
+                s.clone() * (hi - hit.clone() * b),
+                s * (count - hit),
+            ]
+        });
+
+        // The whole sequence lives in one region, so rows chain by rotation instead of copies
+        cs.create_gate("non-membership step", |cells| {
+            let s = cells.query_selector(step);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let b_prev = cells.query_advice(col_b, Rotation::prev());
+            let c_prev = cells.query_advice(col_c, Rotation::prev());
+            let x_cur = cells.query_advice(x, Rotation::cur());
+            let x_prev = cells.query_advice(x, Rotation::prev());
+            let hit = cells.query_advice(hit, Rotation::cur());
+            let [lo_prev, hi_prev, count_prev] =
+                [lo, hi, count].map(|column| cells.query_advice(column, Rotation::prev()));
+            let [lo, hi, count] =
+                [lo, hi, count].map(|column| cells.query_advice(column, Rotation::cur()));
+
+            vec![
+                s.clone() * (a.clone() - b_prev),
+                s.clone() * (b.clone() - c_prev),
+                s.clone() * (x_cur - x_prev),
+                s.clone() * (lo - lo_prev - hit.clone() * a),
+                s.clone() * (hi - hi_prev - hit.clone() * b),
+                s * (count - count_prev - hit),
+            ]
+        });
+
+        cs.create_gate("strictly between", |cells| {
+            let s = cells.query_selector(last);
+            let one = Expression::Constant(F::one());
+            let x = cells.query_advice(x, Rotation::cur());
+            let [lo, hi, count] =
+                [lo, hi, count].map(|column| cells.query_advice(column, Rotation::cur()));
+            let [gap_lo, gap_hi] = gaps.map(|column| cells.query_advice(column, Rotation::cur()));
+
+            vec![
+                s.clone() * (count - one.clone()),
+                s.clone() * (x.clone() - lo - one.clone() - gap_lo),
+                s * (hi - x - one - gap_hi),
+            ]
+        });
+
+        NonMembershipConfig {
+            advice,
+            digits,
+            hit,
+            lo,
+            hi,
+            count,
+            x,
+            gaps,
+            selector,
+            first,
+            step,
+            last,
+            instance,
+        }
+    }
+
+    pub fn load_table(&self, layouter: impl Layouter<F>) -> Result<(), Error> {
+        DigitsChip::construct(self.config.digits.clone()).load_table(layouter)
+    }
+
+    /// Brackets x between consecutive terms among F_1..=F_{fib_size}, with both gaps checked
+    /// to fit in `num_digits` decimal digits
+    pub fn assign_non_membership(
+        &self,
+        mut layouter: impl Layouter<F>,
+        fib_size: usize,
+        num_digits: usize,
+    ) -> Result<(), Error> {
+        let config = &self.config;
+        if fib_size < 2 || fib_size > max_unwrapped_index::<F>() {
+            return Err(Error::Synthesis);
+        }
+        let last = fib_size - 2;
+
+        let gaps = layouter.assign_region(
+            || "non-membership",
+            |mut region| {
+                let mut a = region
+                    .assign_advice_from_instance(|| "a", config.instance, 0, config.advice[0], 0)?
+                    .value()
+                    .copied();
+                let mut b = region
+                    .assign_advice_from_instance(|| "b", config.instance, 1, config.advice[1], 0)?
+                    .value()
+                    .copied();
+                let x = region
+                    .assign_advice_from_instance(|| "x", config.instance, X_ROW, config.x, 0)?
+                    .value()
+                    .copied();
+
+                let zero = Value::known(F::zero());
+                let (mut lo, mut hi, mut count) = (zero, zero, zero);
+
+                for row in 0..=last {
+                    config.selector.enable(&mut region, row)?;
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else {
+                        config.step.enable(&mut region, row)?;
+                        region.assign_advice(|| "a", config.advice[0], row, || a)?;
+                        region.assign_advice(|| "b", config.advice[1], row, || b)?;
+                        region.assign_advice(|| "x", config.x, row, || x)?;
+                    }
+
+                    let c = a + b;
+                    region.assign_advice(|| "c", config.advice[2], row, || c)?;
+
+                    let hit = a.zip(b).zip(x).map(|((a, b), x)| {
+                        let x = to_biguint(x);
+                        if to_biguint(a) < x && x < to_biguint(b) {
+                            F::one()
+                        } else {
+                            F::zero()
+                        }
+                    });
+                    region.assign_advice(|| "hit", config.hit, row, || hit)?;
+
+                    lo = lo + hit * a;
+                    hi = hi + hit * b;
+                    count = count + hit;
+                    region.assign_advice(|| "lo", config.lo, row, || lo)?;
+                    region.assign_advice(|| "hi", config.hi, row, || hi)?;
+                    region.assign_advice(|| "count", config.count, row, || count)?;
+
+                    a = b;
+                    b = c;
+                }
+
+                config.last.enable(&mut region, last)?;
+                let one = Value::known(F::one());
+                let gap_lo =
+                    region.assign_advice(|| "gap lo", config.gaps[0], last, || x - lo - one)?;
+                let gap_hi =
+                    region.assign_advice(|| "gap hi", config.gaps[1], last, || hi - x - one)?;
+                Ok([gap_lo, gap_hi])
+            },
+        )?;
+
+        let digits = DigitsChip::construct(config.digits.clone());
+        for gap in &gaps {
+            digits.assign_digits(layouter.namespace(|| "gap digits"), gap, num_digits)?;
+        }
+        Ok(())
+    }
+}
+
+/// Proves the x at instance row 2 is not among F_1..=F_{fib_size}, for rows 0 and 1 holding
+/// the seeds (1, 1)
+#[derive(Clone, Debug, Default)]
+pub struct NonMembershipCircuit {
+    pub fib_size: usize,
+    pub num_digits: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for NonMembershipCircuit {
+    type Config = NonMembershipConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        NonMembershipChip::configure_non_membership(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = NonMembershipChip::construct(config);
+
+        chip.load_table(layouter.namespace(|| "digit table"))?;
+        chip.assign_non_membership(
+            layouter.namespace(|| "non-membership"),
+            self.fib_size,
+            self.num_digits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    fn run_with_seeds(a: u64, b: u64, x: u64) -> MockProver<Fr> {
+        let circuit = NonMembershipCircuit {
+            fib_size: 12,
+            num_digits: 20,
+        };
+        let instance = vec![Fr::from(a), Fr::from(b), Fr::from(x)];
+        MockProver::run(6, &circuit, vec![instance]).unwrap()
+    }
+
+    fn run(x: u64) -> MockProver<Fr> {
+        run_with_seeds(1, 1, x)
+    }
+
+    #[test]
+    fn not_fibonacci() {
+        // F_10 = 55 < 56 < 89 = F_11
+        assert_satisfied_verbose(&run(56));
+        assert_satisfied_verbose(&run(88));
+
+        // No pair brackets a term, so no row can be marked
+        assert!(run(55).verify().is_err());
+        assert!(run(89).verify().is_err());
+    }
+
+    #[test]
+    fn seeds_are_pinned() {
+        // From (2, 1) the pair (1, 3) brackets 2, a term of the real sequence
+        assert!(run(2).verify().is_err());
+        assert!(run_with_seeds(2, 1, 2).verify().is_err());
+        // Seeds that keep increasing are turned away all the same
+        assert!(run_with_seeds(1, 2, 4).verify().is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b, F_{fib_size}, fib_size]
+const OUTPUT_ROW: usize = 2;
+const SIZE_ROW: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct PaddedConfig {
+    pub fib: FibConfig,
+    /// 1 on rows computing a term, 0 on the padding after them
+    pub active: Column<Advice>,
+    /// Two plus the active rows so far, so the last row holds `fib_size`
+    pub count: Column<Advice>,
+    pub row: Selector,
+    pub first: Selector,
+    pub step: Selector,
+}
+
+/// Lays a sequence of any length out over a fixed `pad_to` rows, so every size shares one
+/// verifying key.
+///
+/// Selectors and copies cannot depend on `fib_size` without changing the key, so each row
+/// carries an `active` flag instead. Active rows follow a + b = c; padding rows are free of
+/// the add gate and only carry the last term forward (c = b), which leaves F_{fib_size} in the
+/// final row. The flags must form a prefix, and their count is exposed as `fib_size`.
+pub struct PaddedFibChip<F: FieldExt> {
+    config: PaddedConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PaddedFibChip<F> {
+    pub fn construct(config: PaddedConfig) -> PaddedFibChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_padded(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> PaddedConfig {
+        let fib = FibChip::configure(advice, instance, cs);
+        let [col_a, col_b, col_c] = advice;
+        let active = cs.advice_column();
+        let count = cs.advice_column();
+        let row = cs.selector();
+        let first = cs.selector();
+        let step = cs.selector();
+
+        cs.enable_equality(count);
+
+        cs.create_gate("padded row", |cells| {
+            let s = cells.query_selector(row);
+            let a = cells.query_advice(col_a, Rotation::cur());
+            let b = cells.query_advice(col_b, Rotation::cur());
+            let c = cells.query_advice(col_c, Rotation::cur());
+            let active = cells.query_advice(active, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * active.clone() * (one.clone() - active.clone()),
+                s.clone() * active.clone() * (a + b.clone() - c.clone()),
+                s * (one - active) * (c - b),
+            ]
+        });
+
+        cs.create_gate("padded first", |cells| {
+            let s = cells.query_selector(first);
+            let active = cells.query_advice(active, Rotation::cur());
+            let count = cells.query_advice(count, Rotation::cur());
+
+            vec![s * (count - active - Expression::Constant(F::from(2)))]
+        });
+
+        cs.create_gate("padded step", |cells| {
+            let s = cells.query_selector(step);
+            let active_prev = cells.query_advice(active, Rotation::prev());
+            let active = cells.query_advice(active, Rotation::cur());
+            let count_prev = cells.query_advice(count, Rotation::prev());
+            let count = cells.query_advice(count, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                // Once a row is padding, every row after it is too
+                s.clone() * (one - active_prev) * active.clone(),
+                s * (count - count_prev - active),
+            ]
+        });
+
+        PaddedConfig {
+            fib,
+            active,
+            count,
+            row,
+            first,
+            step,
+        }
+    }
+
+    /// Assigns `pad_to` rows, the first `fib_size - 2` of them active, and returns the cells
+    /// holding the final term and the count
+    pub fn assign_padded(
+        &self,
+        mut layouter: impl Layouter<F>,
+        fib_size: usize,
+        pad_to: usize,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), Error> {
+        let config = &self.config;
+        let [col_a, col_b, col_c] = config.fib.advice;
+        if fib_size < 3 || fib_size - 2 > pad_to {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "padded",
+            |mut region| {
+                let mut a =
+                    region.assign_advice_from_instance(|| "a", config.fib.instance, 0, col_a, 0)?;
+                let mut b =
+                    region.assign_advice_from_instance(|| "b", config.fib.instance, 1, col_b, 0)?;
+                let mut count = Value::known(F::from(2));
+                let mut out = None;
+
+                for row in 0..pad_to {
+                    config.row.enable(&mut region, row)?;
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else {
+                        config.step.enable(&mut region, row)?;
+                    }
+
+                    let is_active = row + 2 < fib_size;
+                    let c_value = if is_active {
+                        a.value().copied() + b.value()
+                    } else {
+                        b.value().copied()
+                    };
+                    let active = Value::known(if is_active { F::one() } else { F::zero() });
+                    count = count + active;
+
+                    region.assign_advice(|| "active", config.active, row, || active)?;
+                    let count_cell =
+                        region.assign_advice(|| "count", config.count, row, || count)?;
+                    let c = region.assign_advice(|| "c", col_c, row, || c_value)?;
+
+                    if row + 1 < pad_to {
+                        a = b.copy_advice(|| "a", &mut region, col_a, row + 1)?;
+                        b = c.copy_advice(|| "b", &mut region, col_b, row + 1)?;
+                    } else {
+                        out = Some((c, count_cell));
+                    }
+                }
+
+                out.ok_or(Error::Synthesis)
+            },
+        )
+    }
+}
+
+/// Instance: [a, b, F_{fib_size}, fib_size]. Only `pad_to` sets the circuit shape, so any
+/// `fib_size` up to `pad_to + 2` proves under the same keys.
+#[derive(Clone, Debug, Default)]
+pub struct PaddedFibCircuit {
+    pub fib_size: usize,
+    pub pad_to: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for PaddedFibCircuit {
+    type Config = PaddedConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        PaddedFibChip::configure_padded(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = FibChip::construct(config.fib.clone());
+        let chip = PaddedFibChip::construct(config);
+
+        let (out, count) =
+            chip.assign_padded(layouter.namespace(|| "padded"), self.fib_size, self.pad_to)?;
+        fib.expose_public(layouter.namespace(|| "out"), &out, OUTPUT_ROW)?;
+        fib.expose_public(layouter.namespace(|| "fib_size"), &count, SIZE_ROW)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::compute;
+    use crate::prover::{gen_params, gen_pk};
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+    use halo2_base::halo2_proofs::SerdeFormat;
+
+    #[test]
+    fn padded_sizes_share_vk() {
+        let one = Fr::from(1);
+        let params = gen_params(7);
+        let mut vks = vec![];
+
+        for fib_size in [5, 50] {
+            let circuit = PaddedFibCircuit {
+                fib_size,
+                pad_to: 64,
+            };
+            let out = compute(fib_size, one, one);
+            let size = Fr::from(fib_size as u64);
+
+            let prover = MockProver::run(7, &circuit, vec![vec![one, one, out, size]]).unwrap();
+            assert_satisfied_verbose(&prover);
+
+            // Claiming the other length fails the count
+            let prover =
+                MockProver::run(7, &circuit, vec![vec![one, one, out, size + one]]).unwrap();
+            assert!(prover.verify().is_err());
+
+            let pk = gen_pk(&params, &circuit).unwrap();
+            vks.push(pk.get_vk().to_bytes(SerdeFormat::RawBytes));
+        }
+
+        assert_eq!(vks[0], vks[1]);
+    }
+}
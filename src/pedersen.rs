@@ -0,0 +1,326 @@
+use crate::seeds::seeds_from_label;
+use halo2_base::halo2_proofs::{
+    circuit::*,
+    halo2curves::{
+        bn256::Fr,
+        group::ff::{Field, PrimeField},
+    },
+    plonk::*,
+    poly::Rotation,
+};
+
+/// Bits taken from each scalar; every bn256 scalar is below 2^254
+pub const SCALAR_BITS: usize = 254;
+
+/// Affine point on Grumpkin, y^2 = x^3 - 17 over the bn256 scalar field, so its arithmetic
+/// is native to circuits over `Fr`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GrumpkinPoint {
+    pub x: Fr,
+    pub y: Fr,
+}
+
+impl GrumpkinPoint {
+    /// (1, sqrt(-16))
+    pub fn generator() -> Self {
+        Self::lift(Fr::from(1)).unwrap()
+    }
+
+    /// A point whose discrete log relative to the others nobody knows, found by hashing `label`
+    /// to an x coordinate and incrementing until x^3 - 17 is a square
+    pub fn hash_to_curve(label: &str) -> Self {
+        let mut x = seeds_from_label(label).0;
+        loop {
+            if let Some(point) = Self::lift(x) {
+                return point;
+            }
+            x += Fr::from(1);
+        }
+    }
+
+    fn lift(x: Fr) -> Option<Self> {
+        let y: Option<Fr> = (x.square() * x - Fr::from(17)).sqrt().into();
+        y.map(|y| Self { x, y })
+    }
+
+    /// Incomplete addition: `None` when the x coordinates coincide
+    pub fn add(&self, other: &Self) -> Option<Self> {
+        let inv: Option<Fr> = (other.x - self.x).invert().into();
+        let lambda = (other.y - self.y) * inv?;
+        let x = lambda.square() - self.x - other.x;
+        Some(Self {
+            x,
+            y: lambda * (self.x - x) - self.y,
+        })
+    }
+
+    pub fn double(&self) -> Self {
+        let lambda = self.x.square() * Fr::from(3) * self.y.double().invert().unwrap();
+        let x = lambda.square() - self.x.double();
+        Self {
+            x,
+            y: lambda * (self.x - x) - self.y,
+        }
+    }
+}
+
+/// The bases of the seed commitment: `G` and `H` for the seeds, `R` for the blinding and the
+/// offset the accumulator starts from
+pub fn pedersen_bases() -> (GrumpkinPoint, GrumpkinPoint, GrumpkinPoint, GrumpkinPoint) {
+    (
+        GrumpkinPoint::generator(),
+        GrumpkinPoint::hash_to_curve("gpu-test::pedersen::H"),
+        GrumpkinPoint::hash_to_curve("gpu-test::pedersen::R"),
+        GrumpkinPoint::hash_to_curve("gpu-test::pedersen::offset"),
+    )
+}
+
+/// `base, 2 base, 4 base, ...`, one per scalar bit
+fn doublings(base: GrumpkinPoint) -> Vec<GrumpkinPoint> {
+    let mut table = Vec::with_capacity(SCALAR_BITS);
+    let mut cur = base;
+    for _ in 0..SCALAR_BITS {
+        table.push(cur);
+        cur = cur.double();
+    }
+    table
+}
+
+fn scalar_bits(scalar: Fr) -> Vec<bool> {
+    let repr = scalar.to_repr();
+    (0..SCALAR_BITS)
+        .map(|i| (repr.as_ref()[i / 8] >> (i % 8)) & 1 == 1)
+        .collect()
+}
+
+fn accumulate(mut acc: GrumpkinPoint, scalar: Fr, table: &[GrumpkinPoint]) -> GrumpkinPoint {
+    for (bit, base) in scalar_bits(scalar).into_iter().zip(table) {
+        if bit {
+            // Hitting an exceptional case means finding a discrete log relation between the
+            // offset and the bases
+            acc = acc.add(base).expect("exceptional Pedersen addition");
+        }
+    }
+    acc
+}
+
+/// Off-circuit x coordinate of `offset + a G + b H + r R`, matching `PedersenChip::commit`.
+/// A uniformly random `r` makes the commitment hiding as well as binding.
+pub fn pedersen_commit(a: Fr, b: Fr, r: Fr) -> Fr {
+    let (g, h, blind, offset) = pedersen_bases();
+    let acc = accumulate(offset, a, &doublings(g));
+    let acc = accumulate(acc, b, &doublings(h));
+    accumulate(acc, r, &doublings(blind)).x
+}
+
+#[derive(Debug, Clone)]
+pub struct PedersenConfig {
+    pub bit: Column<Advice>,
+    pub lambda: Column<Advice>,
+    /// Accumulator point and scalar running sum; all three need equality
+    pub acc: [Column<Advice>; 2],
+    pub scalar: Column<Advice>,
+    /// `2^i B` for the base in use, and `2^i`
+    pub base: [Column<Fixed>; 2],
+    pub pow2: Column<Fixed>,
+    pub selector: Selector,
+}
+
+/// Fixed-base scalar multiplication on Grumpkin by double-and-add over precomputed doublings.
+///
+/// Row i holds bit i of the scalar, the accumulator before it is applied and `2^i B` in the
+/// fixed columns; the next row holds the accumulator after. Starting from an offset point
+/// keeps the incomplete addition formulas away from the identity. The scalar is recomposed
+/// from its bits alongside and copy-constrained to the input cell. 254 bits can also spell
+/// `a + p` for the smallest `a`, so the decomposition is not forced to be canonical.
+pub struct PedersenChip {
+    config: PedersenConfig,
+}
+
+impl PedersenChip {
+    pub fn construct(config: PedersenConfig) -> Self {
+        Self { config }
+    }
+
+    /// Reuses `advice` for the accumulator and running sum, so those columns must already
+    /// have equality enabled
+    pub fn configure(cs: &mut ConstraintSystem<Fr>, advice: [Column<Advice>; 3]) -> PedersenConfig {
+        let [acc_x, acc_y, scalar] = advice;
+        let bit = cs.advice_column();
+        let lambda = cs.advice_column();
+        let base = [cs.fixed_column(), cs.fixed_column()];
+        let pow2 = cs.fixed_column();
+        let constant = cs.fixed_column();
+        let selector = cs.selector();
+
+        cs.enable_constant(constant);
+
+        cs.create_gate("pedersen step", |cells| {
+            let s = cells.query_selector(selector);
+            let one = Expression::Constant(Fr::one());
+            let bit = cells.query_advice(bit, Rotation::cur());
+            let lambda = cells.query_advice(lambda, Rotation::cur());
+            let ax = cells.query_advice(acc_x, Rotation::cur());
+            let ay = cells.query_advice(acc_y, Rotation::cur());
+            let nx = cells.query_advice(acc_x, Rotation::next());
+            let ny = cells.query_advice(acc_y, Rotation::next());
+            let z = cells.query_advice(scalar, Rotation::cur());
+            let z_next = cells.query_advice(scalar, Rotation::next());
+            let gx = cells.query_fixed(base[0], Rotation::cur());
+            let gy = cells.query_fixed(base[1], Rotation::cur());
+            let pow2 = cells.query_fixed(pow2, Rotation::cur());
+
+            let not_bit = one - bit.clone();
+            vec![
+                s.clone() * bit.clone() * not_bit.clone(),
+                // With the bit set, (nx, ny) = (ax, ay) + (gx, gy); otherwise it is unchanged
+                s.clone()
+                    * bit.clone()
+                    * (lambda.clone() * (gx.clone() - ax.clone()) - (gy - ay.clone())),
+                s.clone()
+                    * (nx.clone()
+                        - bit.clone() * (lambda.clone().square() - ax.clone() - gx)
+                        - not_bit.clone() * ax.clone()),
+                s.clone() * (ny - bit.clone() * (lambda * (ax - nx) - ay.clone()) - not_bit * ay),
+                s * (z_next - z - bit * pow2),
+            ]
+        });
+
+        PedersenConfig {
+            bit,
+            lambda,
+            acc: [acc_x, acc_y],
+            scalar,
+            base,
+            pow2,
+            selector,
+        }
+    }
+
+    /// The x coordinate of `offset + a G + b H + r R`
+    pub fn commit(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        a: &AssignedCell<Fr, Fr>,
+        b: &AssignedCell<Fr, Fr>,
+        r: &AssignedCell<Fr, Fr>,
+    ) -> Result<AssignedCell<Fr, Fr>, Error> {
+        let (g, h, blind, offset) = pedersen_bases();
+
+        let start = layouter.assign_region(
+            || "offset",
+            |mut region| {
+                let x =
+                    region.assign_advice_from_constant(|| "x", self.config.acc[0], 0, offset.x)?;
+                let y =
+                    region.assign_advice_from_constant(|| "y", self.config.acc[1], 0, offset.y)?;
+                Ok((x, y))
+            },
+        )?;
+
+        let acc = self.mul_add(layouter.namespace(|| "a G"), &start, a, &doublings(g))?;
+        let acc = self.mul_add(layouter.namespace(|| "b H"), &acc, b, &doublings(h))?;
+        let acc = self.mul_add(layouter.namespace(|| "r R"), &acc, r, &doublings(blind))?;
+        Ok(acc.0)
+    }
+
+    /// `acc + scalar B`, with `table` the doublings of `B`
+    fn mul_add(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        acc: &(AssignedCell<Fr, Fr>, AssignedCell<Fr, Fr>),
+        scalar: &AssignedCell<Fr, Fr>,
+        table: &[GrumpkinPoint],
+    ) -> Result<(AssignedCell<Fr, Fr>, AssignedCell<Fr, Fr>), Error> {
+        let config = &self.config;
+        let bits = scalar.value().map(|s| scalar_bits(*s));
+
+        layouter.assign_region(
+            || "mul add",
+            |mut region| {
+                let mut x = acc.0.copy_advice(|| "x", &mut region, config.acc[0], 0)?;
+                let mut y = acc.1.copy_advice(|| "y", &mut region, config.acc[1], 0)?;
+                let mut z =
+                    region.assign_advice_from_constant(|| "z", config.scalar, 0, Fr::zero())?;
+                let mut pow2 = Fr::one();
+
+                for (row, base) in table.iter().enumerate() {
+                    config.selector.enable(&mut region, row)?;
+                    region.assign_fixed(|| "gx", config.base[0], row, || Value::known(base.x))?;
+                    region.assign_fixed(|| "gy", config.base[1], row, || Value::known(base.y))?;
+                    region.assign_fixed(|| "2^i", config.pow2, row, || Value::known(pow2))?;
+
+                    let bit = bits.as_ref().map(|bits| bits[row]);
+                    let point = x
+                        .value()
+                        .zip(y.value())
+                        .map(|(x, y)| GrumpkinPoint { x: *x, y: *y });
+                    let lambda = point.map(|p| {
+                        Option::<Fr>::from((base.x - p.x).invert())
+                            .map_or(Fr::zero(), |inv| (base.y - p.y) * inv)
+                    });
+                    let next = point.zip(bit).map(
+                        |(p, bit)| {
+                            if bit {
+                                p.add(base).unwrap_or(p)
+                            } else {
+                                p
+                            }
+                        },
+                    );
+                    let bit = bit.map(|bit| if bit { Fr::one() } else { Fr::zero() });
+
+                    region.assign_advice(|| "bit", config.bit, row, || bit)?;
+                    region.assign_advice(|| "lambda", config.lambda, row, || lambda)?;
+
+                    let next_z = z.value().copied() + bit * Value::known(pow2);
+                    x = region.assign_advice(
+                        || "x",
+                        config.acc[0],
+                        row + 1,
+                        || next.map(|p| p.x),
+                    )?;
+                    y = region.assign_advice(
+                        || "y",
+                        config.acc[1],
+                        row + 1,
+                        || next.map(|p| p.y),
+                    )?;
+                    z = region.assign_advice(|| "z", config.scalar, row + 1, || next_z)?;
+                    pow2 = pow2.double();
+                }
+
+                region.constrain_equal(z.cell(), scalar.cell())?;
+                Ok((x, y))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bases_are_on_curve() {
+        let (g, h, blind, offset) = pedersen_bases();
+        for p in [g, h, blind, offset, g.double(), g.add(&h).unwrap()] {
+            assert_eq!(p.y.square(), p.x.square() * p.x - Fr::from(17));
+        }
+        let (one, two) = (Fr::from(1), Fr::from(2));
+        assert_ne!(
+            pedersen_commit(one, two, one),
+            pedersen_commit(two, one, one)
+        );
+
+        // The same seeds under another blinding land elsewhere
+        assert_ne!(
+            pedersen_commit(one, one, one),
+            pedersen_commit(one, one, two)
+        );
+        assert_ne!(
+            pedersen_commit(one, one, Fr::zero()),
+            pedersen_commit(one, one, two)
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use crate::error;
+use crate::mod_step::{ModStepChip, ModStepConfig};
+#[cfg(feature = "prover")]
+use crate::prover::{gen_pk, prove_fib};
+use crate::prover::{params_for_k, verify_fib};
+use halo2_base::halo2_proofs::{
+    circuit::*,
+    halo2curves::{
+        bn256::{Bn256, Fr},
+        FieldExt,
+    },
+    plonk::*,
+    poly::{kzg::commitment::ParamsKZG, Rotation},
+};
+use std::marker::PhantomData;
+
+// Instance layout: [period]
+const PERIOD_ROW: usize = 0;
+
+/// Off-circuit Pisano period: the first n > 0 with (F_n, F_{n+1}) = (0, 1) mod `modulus`
+pub fn pisano_period(modulus: u64) -> usize {
+    let (mut a, mut b) = (0u64, 1 % modulus);
+    let mut n = 0;
+    loop {
+        (a, b) = (b, (a + b) % modulus);
+        n += 1;
+        if (a, b) == (0, 1 % modulus) {
+            return n;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PisanoConfig {
+    pub sequence: ModStepConfig,
+    /// Inverse of a^2 + (b - 1)^2, showing the pair is not (0, 1)
+    pub inv: Column<Advice>,
+    pub index: Column<Advice>,
+    pub first: Selector,
+    pub mid: Selector,
+    pub last: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// Proves π(m), the period of the Fibonacci sequence reduced mod m.
+///
+/// Row i holds (F_i, F_{i+1}) mod m, stepped by `ModStepChip`, and the index i. The pair
+/// starts at (0, 1), must return to it on the last row, and must differ from it on every row
+/// in between, so the last index is the first return, i.e. the period. The modulus is a
+/// fixed column and so part of the verifying key; m^2 must stay well below the field modulus
+/// for the inequality check.
+pub struct PisanoChip<F: FieldExt> {
+    config: PisanoConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PisanoChip<F> {
+    pub fn construct(config: PisanoConfig) -> PisanoChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_pisano(
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> PisanoConfig {
+        let sequence = ModStepChip::configure_mod_step(cs);
+        let inv = cs.advice_column();
+        let index = cs.advice_column();
+        let first = cs.selector();
+        let mid = cs.selector();
+        let last = cs.selector();
+        let (a, b) = (sequence.a, sequence.b);
+
+        cs.enable_equality(index);
+        cs.enable_equality(instance);
+
+        cs.create_gate("pisano first", |cells| {
+            let s = cells.query_selector(first);
+            let a = cells.query_advice(a, Rotation::cur());
+            let b = cells.query_advice(b, Rotation::cur());
+            let index = cells.query_advice(index, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![s.clone() * a, s.clone() * (b - one), s * index]
+        });
+
+        // The index advances wherever the sequence does
+        cs.create_gate("pisano index", |cells| {
+            let s = cells.query_selector(sequence.step);
+            let index = cells.query_advice(index, Rotation::cur());
+            let index_next = cells.query_advice(index, Rotation::next());
+            let one = Expression::Constant(F::one());
+
+            vec![s * (index_next - index - one)]
+        });
+
+        cs.create_gate("pisano not returned", |cells| {
+            let s = cells.query_selector(mid);
+            let a = cells.query_advice(a, Rotation::cur());
+            let b = cells.query_advice(b, Rotation::cur());
+            let inv = cells.query_advice(inv, Rotation::cur());
+            let one = Expression::Constant(F::one());
+            let b_1 = b - one.clone();
+
+            vec![s * ((a.clone() * a + b_1.clone() * b_1) * inv - one)]
+        });
+
+        cs.create_gate("pisano returned", |cells| {
+            let s = cells.query_selector(last);
+            let a = cells.query_advice(a, Rotation::cur());
+            let b = cells.query_advice(b, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![s.clone() * a, s * (b - one)]
+        });
+
+        PisanoConfig {
+            sequence,
+            inv,
+            index,
+            first,
+            mid,
+            last,
+            instance,
+        }
+    }
+
+    pub fn load_table(&self, layouter: impl Layouter<F>, modulus: u64) -> Result<(), Error> {
+        ModStepChip::construct(self.config.sequence.clone()).load_table(layouter, modulus)
+    }
+
+    /// Assigns rows 0..=`period` of the reduced sequence and returns the last index cell
+    pub fn assign_period(
+        &self,
+        mut layouter: impl Layouter<F>,
+        modulus: u64,
+        period: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = &self.config;
+        if modulus < 2 || period == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "pisano",
+            |mut region| {
+                let sequence = &config.sequence;
+                let step = ModStepChip::construct(sequence.clone());
+                let (mut a, mut b) = (Value::known(F::zero()), Value::known(F::one()));
+                let mut index = None;
+
+                for row in 0..=period {
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else if row < period {
+                        config.mid.enable(&mut region, row)?;
+                    } else {
+                        config.last.enable(&mut region, row)?;
+                    }
+
+                    region.assign_advice(|| "a", sequence.a, row, || a)?;
+                    region.assign_advice(|| "b", sequence.b, row, || b)?;
+                    let i = Value::known(F::from(row as u64));
+                    index = Some(region.assign_advice(|| "index", config.index, row, || i)?);
+
+                    // Zero where the pair is (0, 1); the gate then has no inverse to accept
+                    let dist = a.zip(b).map(|(a, b)| a.square() + (b - F::one()).square());
+                    let inv = dist.map(|dist| dist.invert().unwrap_or(F::zero()));
+                    region.assign_advice(|| "inv", config.inv, row, || inv)?;
+
+                    if row < period {
+                        (a, b) = step.assign_step(&mut region, row, modulus, a, b)?;
+                    }
+                }
+                sequence.range.enable(&mut region, period)?;
+
+                index.ok_or(Error::Synthesis)
+            },
+        )
+    }
+
+    pub fn expose_period(
+        &self,
+        mut layouter: impl Layouter<F>,
+        index: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(index.cell(), self.config.instance, PERIOD_ROW)
+    }
+}
+
+/// Instance: [period]. Both the modulus and the claimed period fix the circuit shape, so
+/// keys are per (modulus, period) and only the correct period is satisfiable.
+#[derive(Clone, Debug, Default)]
+pub struct PisanoCircuit {
+    pub modulus: u64,
+    pub period: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for PisanoCircuit {
+    type Config = PisanoConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let instance = cs.instance_column();
+        PisanoChip::configure_pisano(instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = PisanoChip::construct(config);
+
+        chip.load_table(layouter.namespace(|| "residue table"), self.modulus)?;
+        let index =
+            chip.assign_period(layouter.namespace(|| "pisano"), self.modulus, self.period)?;
+        chip.expose_period(layouter.namespace(|| "period"), &index)
+    }
+}
+
+// 61 rows of the reduced sequence plus the 10-entry residue table fit under 2^7 with blinding
+#[cfg(feature = "prover")]
+const LAST_DIGIT_K: u32 = 7;
+
+/// A proof of π(m), with what a verifier needs to rebuild its key from the same params
+#[derive(Clone, Debug)]
+pub struct PeriodProof {
+    pub modulus: u64,
+    /// The period the proof exposes, at instance row 0
+    pub period: usize,
+    pub k: u32,
+    pub proof: Vec<u8>,
+}
+
+/// Proves that the last digit of the Fibonacci numbers repeats every 60 terms, π(10) = 60.
+///
+/// The period comes from the off-circuit oracle `pisano_period(10)` and is then proven with
+/// `PisanoCircuit`, which reduces the sequence mod 10 and shows (0, 1) first recurs there.
+#[cfg(feature = "prover")]
+pub fn prove_last_digit_period(params: &ParamsKZG<Bn256>) -> Result<PeriodProof, error::Error> {
+    let modulus = 10;
+    let period = pisano_period(modulus);
+    let circuit = PisanoCircuit { modulus, period };
+
+    let params = params_for_k(params, LAST_DIGIT_K)?;
+    let pk = gen_pk(&params, &circuit)?;
+    let instances = vec![vec![Fr::from(period as u64)]];
+    let proof = prove_fib(&params, &pk, circuit, &instances, false)?;
+
+    Ok(PeriodProof {
+        modulus,
+        period,
+        k: LAST_DIGIT_K,
+        proof,
+    })
+}
+
+/// Checks a `PeriodProof` against a key regenerated from its modulus and period, over the
+/// params it was proven with
+pub fn verify_period_proof(
+    params: &ParamsKZG<Bn256>,
+    period_proof: &PeriodProof,
+) -> Result<(), error::Error> {
+    let circuit = PisanoCircuit {
+        modulus: period_proof.modulus,
+        period: period_proof.period,
+    };
+    let params = params_for_k(params, period_proof.k)?;
+    let vk = keygen_vk(&params, &circuit)?;
+    let instances = vec![vec![Fr::from(period_proof.period as u64)]];
+
+    verify_fib(&params, &vk, &instances, &period_proof.proof)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prover::gen_params;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+
+    #[test]
+    fn period_of_ten() {
+        assert_eq!(pisano_period(10), 60);
+
+        let circuit = PisanoCircuit {
+            modulus: 10,
+            period: 60,
+        };
+        let prover = MockProver::<Fr>::run(7, &circuit, vec![vec![Fr::from(60)]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        // 120 also returns to (0, 1), but passes through it at row 60 on the way
+        let circuit = PisanoCircuit {
+            modulus: 10,
+            period: 120,
+        };
+        let prover = MockProver::<Fr>::run(8, &circuit, vec![vec![Fr::from(120)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        let circuit = PisanoCircuit {
+            modulus: 10,
+            period: 59,
+        };
+        let prover = MockProver::<Fr>::run(7, &circuit, vec![vec![Fr::from(59)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn last_digit_repeats_every_60() {
+        let params = gen_params(LAST_DIGIT_K);
+        let period_proof = prove_last_digit_period(&params).unwrap();
+        assert_eq!(period_proof.period, 60);
+        verify_period_proof(&params, &period_proof).unwrap();
+
+        // The same proof does not vouch for a neighbouring period
+        let wrong = PeriodProof {
+            period: 59,
+            ..period_proof
+        };
+        assert!(verify_period_proof(&params, &wrong).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+/// Field element for a signed coefficient
+pub fn coeff<F: FieldExt>(c: i64) -> F {
+    let abs = F::from(c.unsigned_abs());
+    if c < 0 {
+        -abs
+    } else {
+        abs
+    }
+}
+
+/// Off-circuit p(x) for coefficients lowest degree first
+pub fn eval_poly<F: FieldExt>(coeffs: &[i64], x: F) -> F {
+    coeffs
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, c| acc * x + coeff::<F>(*c))
+}
+
+#[derive(Debug, Clone)]
+pub struct PredicateConfig {
+    pub fib: FibConfig,
+    /// The input, copied onto every row
+    pub x: Column<Advice>,
+    /// Horner accumulator
+    pub acc: Column<Advice>,
+    /// Coefficients, highest degree on row 0
+    pub coeff: Column<Fixed>,
+    pub first: Selector,
+    pub step: Selector,
+    pub zero: Selector,
+}
+
+/// Constrains p(x) = 0 for a polynomial whose coefficients sit in a fixed column.
+///
+/// Row i holds coefficient `deg - i`, and `acc` runs Horner's rule down the region
+/// (`acc = acc_prev * x + coeff`), so the last row holds p(x) and is constrained to zero. The
+/// coefficients are assigned at synthesis but live in a fixed column, so the polynomial is part
+/// of the verifying key and the prover cannot swap it.
+pub struct PredicateChip<F: FieldExt> {
+    config: PredicateConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PredicateChip<F> {
+    pub fn construct(config: PredicateConfig) -> PredicateChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_predicate(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> PredicateConfig {
+        let fib = FibChip::configure(advice, instance, cs);
+        let x = cs.advice_column();
+        let acc = cs.advice_column();
+        let coeff = cs.fixed_column();
+        let first = cs.selector();
+        let step = cs.selector();
+        let zero = cs.selector();
+
+        cs.enable_equality(x);
+
+        cs.create_gate("horner first", |cells| {
+            let s = cells.query_selector(first);
+            let acc = cells.query_advice(acc, Rotation::cur());
+            let coeff = cells.query_fixed(coeff, Rotation::cur());
+
+            vec![s * (acc - coeff)]
+        });
+
+        cs.create_gate("horner step", |cells| {
+            let s = cells.query_selector(step);
+            let x = cells.query_advice(x, Rotation::cur());
+            let acc = cells.query_advice(acc, Rotation::cur());
+            let acc_prev = cells.query_advice(acc, Rotation::prev());
+            let coeff = cells.query_fixed(coeff, Rotation::cur());
+
+            vec![s * (acc_prev * x + coeff - acc)]
+        });
+
+        cs.create_gate("predicate holds", |cells| {
+            let s = cells.query_selector(zero);
+            let acc = cells.query_advice(acc, Rotation::cur());
+
+            vec![s * acc]
+        });
+
+        PredicateConfig {
+            fib,
+            x,
+            acc,
+            coeff,
+            first,
+            step,
+            zero,
+        }
+    }
+
+    /// Evaluates the polynomial with `coeffs`, lowest degree first, at `x` and constrains the
+    /// result to zero
+    pub fn assign_predicate(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &AssignedCell<F, F>,
+        coeffs: &[i64],
+    ) -> Result<(), Error> {
+        let config = &self.config;
+        if coeffs.is_empty() {
+            return Err(Error::Synthesis);
+        }
+        let last = coeffs.len() - 1;
+
+        layouter.assign_region(
+            || "predicate",
+            |mut region| {
+                let mut acc = Value::known(F::zero());
+
+                for (row, c) in coeffs.iter().rev().enumerate() {
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else {
+                        config.step.enable(&mut region, row)?;
+                    }
+                    if row == last {
+                        config.zero.enable(&mut region, row)?;
+                    }
+
+                    let c = coeff::<F>(*c);
+                    region.assign_fixed(|| "coeff", config.coeff, row, || Value::known(c))?;
+                    let x = x.copy_advice(|| "x", &mut region, config.x, row)?;
+
+                    acc = acc * x.value() + Value::known(c);
+                    region.assign_advice(|| "acc", config.acc, row, || acc)?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Proves p(F_{fib_size}) = 0 for the seeds in the instance without exposing F_{fib_size}
+#[derive(Clone, Debug, Default)]
+pub struct PredicateCircuit {
+    pub fib_size: usize,
+    /// Lowest degree first, so `[-55, 1]` is x - 55
+    pub coeffs: Vec<i64>,
+}
+
+impl<F: FieldExt> Circuit<F> for PredicateCircuit {
+    type Config = PredicateConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    // The coefficients go into a fixed column, so they are shape and stay
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        PredicateChip::configure_predicate(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        let fib = FibChip::construct(config.fib.clone());
+        let (_, mut b, mut c) = fib.assign_first_row(layouter.namespace(|| "first row"))?;
+        for _ in 3..self.fib_size {
+            let new_c = fib.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            b = c;
+            c = new_c;
+        }
+
+        let chip = PredicateChip::construct(config);
+        chip.assign_predicate(layouter.namespace(|| "predicate"), &c, &self.coeffs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    fn run(coeffs: Vec<i64>) -> MockProver<Fr> {
+        let circuit = PredicateCircuit {
+            fib_size: 10,
+            coeffs,
+        };
+        let one = Fr::from(1);
+        MockProver::run(5, &circuit, vec![vec![one, one]]).unwrap()
+    }
+
+    #[test]
+    fn f_10_is_root() {
+        // F_10 = 55
+        assert_satisfied_verbose(&run(vec![-55, 1]));
+        assert!(run(vec![-56, 1]).verify().is_err());
+
+        // x^2 - 55x vanishes at 55 too, x^2 - 55 does not
+        assert_satisfied_verbose(&run(vec![0, -55, 1]));
+        assert!(run(vec![-55, 0, 1]).verify().is_err());
+        assert_eq!(
+            eval_poly(&[-55, 0, 1], Fr::from(55)),
+            Fr::from(55u64 * 55 - 55)
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use crate::circuit::compute;
+use crate::pedersen::{pedersen_commit, PedersenChip, PedersenConfig};
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{
+    circuit::*,
+    halo2curves::{bn256::Fr, FieldExt},
+    plonk::*,
+};
+use halo2_gadgets::poseidon::{
+    primitives::{self as poseidon, ConstantLength, Spec},
+    Hash, Pow5Chip, Pow5Config,
+};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+// Instance layout: [seed_commitment, output]
+const COMMITMENT_ROW: usize = 0;
+const OUTPUT_ROW: usize = 1;
+
+/// Poseidon over a ~254-bit field with width 3, rate 2 and the x^5 S-box; round counts follow
+/// the 128-bit security parameters used for Pasta's P128Pow5T3
+#[derive(Debug, Clone, Copy)]
+pub struct PoseidonSpec;
+
+impl<F: FieldExt> Spec<F, 3, 2> for PoseidonSpec {
+    fn full_rounds() -> usize {
+        8
+    }
+
+    fn partial_rounds() -> usize {
+        57
+    }
+
+    fn sbox(val: F) -> F {
+        val.pow_vartime([5])
+    }
+
+    fn secure_mds() -> usize {
+        0
+    }
+}
+
+/// Two-to-one Poseidon hash, matching `PoseidonCommit::hash_cells`
+pub fn poseidon_hash<F: FieldExt>(a: F, b: F) -> F {
+    poseidon::Hash::<F, PoseidonSpec, ConstantLength<2>, 3, 2>::init().hash([a, b])
+}
+
+/// Off-circuit commitment matching what `configure_private_seeds` exposes: H(H(a, b), r)
+/// with the blinding `r`
+pub fn commit_seeds<F: FieldExt>(a: F, b: F, r: F) -> F {
+    poseidon_hash(poseidon_hash(a, b), r)
+}
+
+/// Hiding commitment binding the two seeds into the one public value of `PrivateSeedsCircuit`.
+///
+/// The blinding `r` is a private witness the prover draws at random. Without it the
+/// commitment is a deterministic function of the seeds, so low-entropy seeds such as the
+/// standard (1, 1) could be confirmed or brute-forced from it.
+pub trait SeedCommit<F: FieldExt> {
+    type Config: Clone + Debug;
+
+    /// `advice` are the sequence columns, already enabled for equality, for the hash to share
+    fn configure(cs: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> Self::Config;
+
+    /// Off-circuit digest, equal to what `commit_cells` assigns
+    fn commit(a: F, b: F, r: F) -> F;
+
+    fn commit_cells(
+        config: Self::Config,
+        layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+        r: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error>;
+}
+
+/// `commit_seeds`, the Poseidon sponge from halo2_gadgets
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PoseidonCommit;
+
+impl PoseidonCommit {
+    /// `poseidon_hash` in-circuit
+    pub fn hash_cells<F: FieldExt>(
+        config: Pow5Config<F, 3, 2>,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let hasher = Hash::<_, _, PoseidonSpec, ConstantLength<2>, 3, 2>::init(
+            Pow5Chip::construct(config),
+            layouter.namespace(|| "init"),
+        )?;
+        hasher.hash(layouter.namespace(|| "hash"), [a.clone(), b.clone()])
+    }
+}
+
+impl<F: FieldExt> SeedCommit<F> for PoseidonCommit {
+    type Config = Pow5Config<F, 3, 2>;
+
+    fn configure(cs: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> Self::Config {
+        // The sponge state reuses the sequence columns; only the S-box column is extra
+        let partial_sbox = cs.advice_column();
+        let rc_a = [cs.fixed_column(), cs.fixed_column(), cs.fixed_column()];
+        let rc_b = [cs.fixed_column(), cs.fixed_column(), cs.fixed_column()];
+        cs.enable_constant(rc_b[0]);
+
+        Pow5Chip::configure::<PoseidonSpec>(cs, advice, partial_sbox, rc_a, rc_b)
+    }
+
+    fn commit(a: F, b: F, r: F) -> F {
+        commit_seeds(a, b, r)
+    }
+
+    fn commit_cells(
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+        r: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let seeds = Self::hash_cells(config.clone(), layouter.namespace(|| "seeds"), a, b)?;
+        Self::hash_cells(config, layouter.namespace(|| "blind"), &seeds, r)
+    }
+}
+
+/// `pedersen_commit`, the x coordinate of a Pedersen commitment on Grumpkin. Only defined over the
+/// bn256 scalar field, which is Grumpkin's base field.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PedersenCommit;
+
+impl SeedCommit<Fr> for PedersenCommit {
+    type Config = PedersenConfig;
+
+    fn configure(cs: &mut ConstraintSystem<Fr>, advice: [Column<Advice>; 3]) -> Self::Config {
+        PedersenChip::configure(cs, advice)
+    }
+
+    fn commit(a: Fr, b: Fr, r: Fr) -> Fr {
+        pedersen_commit(a, b, r)
+    }
+
+    fn commit_cells(
+        config: Self::Config,
+        layouter: impl Layouter<Fr>,
+        a: &AssignedCell<Fr, Fr>,
+        b: &AssignedCell<Fr, Fr>,
+        r: &AssignedCell<Fr, Fr>,
+    ) -> Result<AssignedCell<Fr, Fr>, Error> {
+        PedersenChip::construct(config).commit(layouter, a, b, r)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PrivateSeedsConfig<F: FieldExt, C: SeedCommit<F>> {
+    pub fib: FibConfig,
+    pub commit: C::Config,
+}
+
+/// Fibonacci with private seeds, public only through `C::commit(a, b, r)` at instance row 0
+/// and, with `expose_output`, F_{fib_size} at row 1
+#[derive(Clone, Debug, Default)]
+pub struct PrivateSeedsCircuit<F, C = PoseidonCommit> {
+    pub seeds: (Value<F>, Value<F>),
+    /// The commitment's blinding, kept private alongside the seeds
+    pub blinding: Value<F>,
+    pub fib_size: usize,
+    pub expose_output: bool,
+    _commit: PhantomData<C>,
+}
+
+impl<F: FieldExt> PrivateSeedsCircuit<F> {
+    pub fn new(a: F, b: F, r: F, fib_size: usize) -> Self {
+        Self::with_commit(a, b, r, fib_size)
+    }
+}
+
+impl<F: FieldExt, C: SeedCommit<F>> PrivateSeedsCircuit<F, C> {
+    /// `r` should be drawn uniformly at random and kept with the seeds to reveal them later
+    pub fn with_commit(a: F, b: F, r: F, fib_size: usize) -> Self {
+        Self {
+            seeds: (Value::known(a), Value::known(b)),
+            blinding: Value::known(r),
+            fib_size,
+            expose_output: false,
+            _commit: PhantomData,
+        }
+    }
+
+    /// Checks seeds and blinding revealed after the fact against a verified instance: they
+    /// must open the commitment and, when the output was exposed, the seeds must run to it
+    pub fn reveal_matches(instance: &[F], fib_size: usize, a: F, b: F, r: F) -> bool {
+        let opens = instance.get(COMMITMENT_ROW) == Some(&C::commit(a, b, r));
+        let output = instance.get(OUTPUT_ROW);
+        opens && output.map_or(true, |output| *output == compute(fib_size, a, b))
+    }
+
+    pub fn configure_private_seeds(cs: &mut ConstraintSystem<F>) -> PrivateSeedsConfig<F, C> {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+        let fib = FibChip::configure(advice, instance, cs);
+        let commit = C::configure(cs, advice);
+
+        PrivateSeedsConfig { fib, commit }
+    }
+}
+
+impl<F: FieldExt, C: SeedCommit<F> + Clone> Circuit<F> for PrivateSeedsCircuit<F, C> {
+    type Config = PrivateSeedsConfig<F, C>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            seeds: (Value::unknown(), Value::unknown()),
+            blinding: Value::unknown(),
+            fib_size: self.fib_size,
+            expose_output: self.expose_output,
+            _commit: PhantomData,
+        }
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        Self::configure_private_seeds(cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        let (a, b, r) = layouter.assign_region(
+            || "seeds",
+            |mut region| {
+                let a = region.assign_advice(|| "a", config.fib.advice[0], 0, || self.seeds.0)?;
+                let b = region.assign_advice(|| "b", config.fib.advice[1], 0, || self.seeds.1)?;
+                let r = region.assign_advice(|| "r", config.fib.advice[2], 0, || self.blinding)?;
+                Ok((a, b, r))
+            },
+        )?;
+
+        let commitment =
+            C::commit_cells(config.commit, layouter.namespace(|| "commit"), &a, &b, &r)?;
+
+        let chip = FibChip::construct(config.fib);
+        chip.expose_public(
+            layouter.namespace(|| "commitment"),
+            &commitment,
+            COMMITMENT_ROW,
+        )?;
+
+        let (_, mut b, mut c) =
+            chip.assign_first_row_from_cells(layouter.namespace(|| "first row"), &a, &b)?;
+        for _ in 3..self.fib_size {
+            let new_c = chip.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            b = c;
+            c = new_c;
+        }
+
+        chip.try_expose_public(
+            layouter.namespace(|| "out"),
+            &c,
+            self.expose_output.then_some(OUTPUT_ROW),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prover::{gen_params, gen_pk, prove_fib, verify_fib};
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+
+    #[test]
+    fn private_seeds_commitment() {
+        let (a, b, r) = (Fr::from(1), Fr::from(1), Fr::from(0x5eed));
+        let circuit = PrivateSeedsCircuit::new(a, b, r, 10);
+
+        let commitment = commit_seeds(a, b, r);
+        let prover = MockProver::run(8, &circuit, vec![vec![commitment]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let other = commit_seeds(a, Fr::from(2), r);
+        let prover = MockProver::run(8, &circuit, vec![vec![other]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn blinding_hides_seeds() {
+        let (a, b) = (Fr::from(1), Fr::from(1));
+        let (r, s) = (Fr::from(7), Fr::from(8));
+
+        // The standard seeds can no longer be confirmed by hashing them
+        assert_ne!(commit_seeds(a, b, r), commit_seeds(a, b, s));
+        assert_ne!(commit_seeds(a, b, r), poseidon_hash(a, b));
+
+        // The statement fixes the commitment, so a proof under r says nothing under s
+        let circuit = PrivateSeedsCircuit::new(a, b, r, 10);
+        let prover = MockProver::run(8, &circuit, vec![vec![commit_seeds(a, b, s)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn commitment_schemes() {
+        let (a, b, r) = (Fr::from(3), Fr::from(5), Fr::from(0x5eed));
+
+        let circuit = PrivateSeedsCircuit::<_, PoseidonCommit>::with_commit(a, b, r, 10);
+        let digest = PoseidonCommit::commit(a, b, r);
+        assert_eq!(digest, commit_seeds(a, b, r));
+        let prover = MockProver::run(8, &circuit, vec![vec![digest]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        // Three 255-row scalar multiplications
+        let circuit = PrivateSeedsCircuit::<_, PedersenCommit>::with_commit(a, b, r, 10);
+        let digest = PedersenCommit::commit(a, b, r);
+        assert_ne!(digest, commit_seeds(a, b, r));
+        let prover = MockProver::run(10, &circuit, vec![vec![digest]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let swapped = PedersenCommit::commit(b, a, r);
+        let prover = MockProver::run(10, &circuit, vec![vec![swapped]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // The blinding is constrained like the seeds
+        let reblinded = PedersenCommit::commit(a, b, r + Fr::from(1));
+        let prover = MockProver::run(10, &circuit, vec![vec![reblinded]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn commitment_and_output() {
+        let (a, b, r) = (Fr::from(3), Fr::from(5), Fr::from(0x5eed));
+        let mut circuit = PrivateSeedsCircuit::new(a, b, r, 10);
+        circuit.expose_output = true;
+
+        let params = gen_params(8);
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instance = vec![commit_seeds(a, b, r), compute(10, a, b)];
+        let proof = prove_fib(&params, &pk, circuit, &[instance.clone()], false).unwrap();
+        verify_fib(&params, pk.get_vk(), &[instance.clone()], &proof).unwrap();
+
+        // The proof pins an output, so claiming another one for the same commitment fails
+        let wrong = vec![instance[0], instance[1] + Fr::from(1)];
+        assert!(verify_fib(&params, pk.get_vk(), &[wrong], &proof).is_err());
+
+        // Later the owner reveals the seeds and the blinding
+        let reveal = PrivateSeedsCircuit::<Fr>::reveal_matches;
+        assert!(reveal(&instance, 10, a, b, r));
+        assert!(!reveal(&instance, 10, b, a, r));
+        assert!(!reveal(&instance, 10, a, b, r + Fr::from(1)));
+        assert!(!reveal(&instance, 11, a, b, r));
+    }
+}
@@ -0,0 +1,146 @@
+//! Rough split of proving time between FFTs, MSMs and everything else.
+//!
+//! `create_proof` has no hooks around its phases, so the split is estimated: one of each
+//! operation the prover repeats (an inverse FFT over the 2^k domain, an FFT into the extended
+//! domain, and a commitment MSM) is timed on the same domain and params, multiplied by how
+//! many the constraint system calls for, and subtracted from the measured total. Only available
+//! with the `profiling` feature.
+
+use crate::error::Error;
+use crate::prover::prove_fib;
+use halo2_base::halo2_proofs::{
+    halo2curves::{
+        bn256::{Bn256, Fr, G1Affine},
+        group::ff::Field,
+    },
+    plonk::{Circuit, ProvingKey},
+    poly::{
+        commitment::{Blind, Params},
+        kzg::commitment::ParamsKZG,
+    },
+};
+use rand::rngs::OsRng;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProveProfile {
+    pub fft_ms: f64,
+    pub msm_ms: f64,
+    pub other_ms: f64,
+}
+
+impl ProveProfile {
+    pub fn total_ms(&self) -> f64 {
+        self.fft_ms + self.msm_ms + self.other_ms
+    }
+}
+
+/// Operation counts for one proof, from the shape of the constraint system
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ProofOps {
+    /// Polynomials the prover interpolates, extends and commits: instance and advice columns,
+    /// three per lookup and one per permutation chunk
+    polys: usize,
+    /// Commitments beyond those: the vanishing argument's random polynomial and quotient
+    /// pieces, and SHPLONK's two opening commitments
+    extra_commitments: usize,
+}
+
+fn op_counts(pk: &ProvingKey<G1Affine>) -> ProofOps {
+    let cs = pk.get_vk().cs();
+    let chunk = cs.degree().saturating_sub(2).max(1);
+    let permutation_columns = cs.permutation().get_columns().len();
+    let permutation_chunks = (permutation_columns + chunk - 1) / chunk;
+
+    ProofOps {
+        polys: cs.num_instance_columns()
+            + cs.num_advice_columns()
+            + 3 * cs.lookups().len()
+            + permutation_chunks,
+        extra_commitments: 1 + (cs.degree() - 1) + 2,
+    }
+}
+
+/// `prove_fib` (SHPLONK, no mock check) with the estimated time split alongside the proof
+pub fn prove_fib_profiled<C: Circuit<Fr>>(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: C,
+    instances: &[Vec<Fr>],
+) -> Result<(Vec<u8>, ProveProfile), Error> {
+    let start = Instant::now();
+    let proof = prove_fib(params, pk, circuit, instances, false)?;
+    let total = start.elapsed();
+
+    let domain = pk.get_vk().get_domain();
+    let mut values = domain.empty_lagrange();
+    for value in values.iter_mut() {
+        *value = Fr::random(OsRng);
+    }
+
+    let start = Instant::now();
+    black_box(params.commit_lagrange(&values, Blind::default()));
+    let msm = start.elapsed();
+
+    let start = Instant::now();
+    let coeffs = domain.lagrange_to_coeff(values);
+    let ifft = start.elapsed();
+
+    let start = Instant::now();
+    let extended = domain.coeff_to_extended(coeffs);
+    let fft = start.elapsed();
+
+    // The quotient comes back out of the extended domain once
+    let start = Instant::now();
+    black_box(domain.extended_to_coeff(extended));
+    let quotient_ifft = start.elapsed();
+
+    let counts = op_counts(pk);
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    let fft_ms = counts.polys as f64 * ms(ifft + fft) + ms(quotient_ifft);
+    let msm_ms = (counts.polys + counts.extra_commitments) as f64 * ms(msm);
+    // The estimates can overshoot on tiny circuits, where fixed overhead dominates
+    let other_ms = (ms(total) - fft_ms - msm_ms).max(0.0);
+
+    Ok((
+        proof,
+        ProveProfile {
+            fft_ms,
+            msm_ms,
+            other_ms,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::FibCircuit;
+    use crate::prover::{gen_params, gen_pk, min_k_for, verify_fib};
+
+    #[test]
+    fn profile_adds_up() {
+        let circuit = FibCircuit {
+            fib_size: 1 << 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+
+        let start = Instant::now();
+        let (proof, profile) = prove_fib_profiled(&params, &pk, circuit, &instances).unwrap();
+        let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+        verify_fib(&params, pk.get_vk(), &instances, &proof).unwrap();
+
+        assert!(profile.fft_ms > 0.0 && profile.msm_ms > 0.0);
+        // The parts add up to the proof alone; the call also spends one of each operation on
+        // calibration, a small fraction next to the proof's dozens
+        let total = profile.total_ms();
+        assert!(
+            (elapsed / 4.0..=elapsed).contains(&total),
+            "profiled {total:.1} ms of a {elapsed:.1} ms call"
+        );
+    }
+}
@@ -0,0 +1,1538 @@
+use crate::circuit::{compute, rows_required, FibCircuit, OUTPUT_ROW};
+use crate::encoding::{decode_instance, encode_instance, take, take_array, Endianness};
+use crate::error::Error;
+use crate::spec::CircuitSpec;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use halo2_base::halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{self, verify_proof, Circuit, ConstraintSystem, VerifyingKey},
+    poly::{
+        commitment::{Params, ParamsProver, Verifier},
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{VerifierGWC, VerifierSHPLONK},
+            strategy::SingleStrategy,
+        },
+        VerificationStrategy,
+    },
+    transcript::{Blake2bRead, Challenge255, Keccak256Read, TranscriptReadBuffer},
+};
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "prover")]
+use halo2_base::halo2_proofs::{
+    dev::MockProver,
+    plonk::{create_proof, keygen_pk, keygen_vk, ProvingKey},
+    poly::{
+        commitment::Prover,
+        kzg::multiopen::{ProverGWC, ProverSHPLONK},
+    },
+    transcript::{Blake2bWrite, Keccak256Write, TranscriptWriterBuffer},
+};
+#[cfg(feature = "prover")]
+use rand::rngs::OsRng;
+#[cfg(feature = "prover")]
+use rayon::prelude::*;
+#[cfg(feature = "prover")]
+use std::io::Write;
+#[cfg(feature = "prover")]
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Written into every encoded bundle; bumped whenever the layout of `to_bytes` changes
+pub const BUNDLE_VERSION: u16 = 1;
+
+/// Bundle versions `ProofBundle::from_bytes` knows how to read
+pub const SUPPORTED_BUNDLE_VERSIONS: RangeInclusive<u16> = 1..=BUNDLE_VERSION;
+
+/// A proof together with the instance it was made against, the `k` of its setup and the spec
+/// of the circuit that produced it
+#[derive(Clone, Debug)]
+pub struct ProofBundle {
+    pub version: u16,
+    pub k: u32,
+    pub spec: CircuitSpec,
+    pub instances: Vec<Vec<Fr>>,
+    pub proof: Vec<u8>,
+}
+
+impl ProofBundle {
+    /// `[version: u16][k: u32][spec len: u32][spec JSON][instance len: u32][instance][proof]`,
+    /// integers little-endian and the instance as `encode_instance` writes it
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let spec = serde_json::to_vec(&self.spec)
+            .map_err(|err| Error::InvalidEncoding(format!("spec: {err}")))?;
+        let instances = encode_instance(&self.instances, Endianness::Little);
+
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.extend_from_slice(&self.k.to_le_bytes());
+        bytes.extend_from_slice(&(spec.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&spec);
+        bytes.extend_from_slice(&(instances.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&instances);
+        bytes.extend_from_slice(&self.proof);
+        Ok(bytes)
+    }
+
+    /// Reads a `to_bytes` bundle, checking the version before anything else so a newer
+    /// layout is rejected instead of misparsed
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = bytes;
+
+        let version = u16::from_le_bytes(take_array(&mut reader)?);
+        if !SUPPORTED_BUNDLE_VERSIONS.contains(&version) {
+            return Err(Error::UnsupportedBundleVersion {
+                found: version,
+                supported: SUPPORTED_BUNDLE_VERSIONS,
+            });
+        }
+        let k = u32::from_le_bytes(take_array(&mut reader)?);
+
+        let len = u32::from_le_bytes(take_array(&mut reader)?) as usize;
+        let spec = serde_json::from_slice(take(&mut reader, len)?)
+            .map_err(|err| Error::InvalidEncoding(format!("spec: {err}")))?;
+
+        let len = u32::from_le_bytes(take_array(&mut reader)?) as usize;
+        let instances = decode_instance(take(&mut reader, len)?, Endianness::Little)?;
+
+        Ok(Self {
+            version,
+            k,
+            spec,
+            instances,
+            proof: reader.to_vec(),
+        })
+    }
+}
+
+/// Written as the first byte of every proof; bumped whenever the header layout changes
+pub const PROOF_FORMAT_VERSION: u8 = 1;
+
+/// KZG multi-open argument a proof was made with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultiOpen {
+    Shplonk = 0,
+    Gwc = 1,
+}
+
+/// Hash behind the Fiat-Shamir transcript a proof was made with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranscriptHash {
+    Blake2b = 0,
+    /// What an EVM verifier can recompute cheaply
+    Keccak256 = 1,
+}
+
+/// Three bytes in front of every proof from `prove_fib_with`: the format version, the
+/// multi-open scheme and the transcript hash, so `verify_fib` can pick the matching verifier
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProofHeader {
+    pub scheme: MultiOpen,
+    pub transcript: TranscriptHash,
+}
+
+impl ProofHeader {
+    pub const LEN: usize = 3;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        [
+            PROOF_FORMAT_VERSION,
+            self.scheme as u8,
+            self.transcript as u8,
+        ]
+    }
+
+    /// Splits `proof` into its header and the transcript bytes that follow
+    pub fn parse(proof: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let mut reader = proof;
+        let [version, scheme, transcript] = take_array(&mut reader)?;
+        if version != PROOF_FORMAT_VERSION {
+            return Err(Error::InvalidEncoding(format!(
+                "proof format version {version}"
+            )));
+        }
+        let scheme = match scheme {
+            0 => MultiOpen::Shplonk,
+            1 => MultiOpen::Gwc,
+            other => return Err(Error::InvalidEncoding(format!("multi-open scheme {other}"))),
+        };
+        let transcript = match transcript {
+            0 => TranscriptHash::Blake2b,
+            1 => TranscriptHash::Keccak256,
+            other => return Err(Error::InvalidEncoding(format!("transcript hash {other}"))),
+        };
+        Ok((Self { scheme, transcript }, reader))
+    }
+}
+
+/// The header tag for a KZG multi-open prover or verifier
+pub trait MultiOpenTag {
+    const SCHEME: MultiOpen;
+}
+
+#[cfg(feature = "prover")]
+impl MultiOpenTag for ProverSHPLONK<'_, Bn256> {
+    const SCHEME: MultiOpen = MultiOpen::Shplonk;
+}
+
+#[cfg(feature = "prover")]
+impl MultiOpenTag for ProverGWC<'_, Bn256> {
+    const SCHEME: MultiOpen = MultiOpen::Gwc;
+}
+
+impl MultiOpenTag for VerifierSHPLONK<'_, Bn256> {
+    const SCHEME: MultiOpen = MultiOpen::Shplonk;
+}
+
+impl MultiOpenTag for VerifierGWC<'_, Bn256> {
+    const SCHEME: MultiOpen = MultiOpen::Gwc;
+}
+
+/// The header tag for a transcript writer
+#[cfg(feature = "prover")]
+pub trait TranscriptTag {
+    const HASH: TranscriptHash;
+}
+
+#[cfg(feature = "prover")]
+impl<W: Write> TranscriptTag for Blake2bWrite<W, G1Affine, Challenge255<G1Affine>> {
+    const HASH: TranscriptHash = TranscriptHash::Blake2b;
+}
+
+#[cfg(feature = "prover")]
+impl<W: Write> TranscriptTag for Keccak256Write<W, G1Affine, Challenge255<G1Affine>> {
+    const HASH: TranscriptHash = TranscriptHash::Keccak256;
+}
+
+/// Smallest `k` whose usable rows fit a `fib_size` sequence.
+///
+/// The last `blinding_factors() + 1` rows of every column are reserved by the prover, so they
+/// are added on top of the rows the chip assigns. halo2 derives that reservation from how many
+/// times an advice column is queried, never from the gate degree, so there is no knob here
+/// to widen it: a minimum degree would change the extended domain but not a single row.
+pub fn min_k_for(fib_size: usize) -> u32 {
+    let cs = constraint_system();
+    let rows = rows_required(fib_size)
+        .saturating_add(cs.blinding_factors() + 1)
+        .max(cs.minimum_rows());
+
+    rows.checked_next_power_of_two()
+        .map_or(usize::BITS, usize::trailing_zeros)
+}
+
+/// The constraint system `FibCircuit` configures
+pub fn constraint_system() -> ConstraintSystem<Fr> {
+    let mut cs = ConstraintSystem::default();
+    <FibCircuit as Circuit<Fr>>::configure(&mut cs);
+    cs
+}
+
+// Trusted setup for testing only - real deployments should load ceremony params.
+// The trapdoor is drawn from OsRng and dropped, so a verifier has to be handed these params
+// (`Params::write`) rather than regenerate them.
+#[cfg(feature = "prover")]
+pub fn gen_params(k: u32) -> ParamsKZG<Bn256> {
+    ParamsKZG::<Bn256>::setup(k, OsRng)
+}
+
+/// Setup from a fixed seed, so any party can rebuild the same params from `k` alone.
+///
+/// Rebuilding them means knowing the trapdoor, and with it forging a proof of anything, so
+/// this only exists for tests that need params to agree across calls.
+#[cfg(all(feature = "prover", any(test, feature = "test-util")))]
+pub fn insecure_seeded_params(k: u32) -> ParamsKZG<Bn256> {
+    use rand::SeedableRng;
+
+    ParamsKZG::<Bn256>::setup(k, rand_chacha::ChaCha20Rng::from_seed(Default::default()))
+}
+
+/// `params` cut down to `2^k` rows. A KZG setup's prefix is a setup for every smaller `k`, so
+/// one large set of params serves every circuit that fits it.
+pub fn params_for_k(params: &ParamsKZG<Bn256>, k: u32) -> Result<ParamsKZG<Bn256>, Error> {
+    if k > params.k() {
+        return Err(plonk::Error::NotEnoughRowsAvailable {
+            current_k: params.k(),
+        }
+        .into());
+    }
+    let mut params = params.clone();
+    if k < params.k() {
+        params.downsize(k);
+    }
+    Ok(params)
+}
+
+#[cfg(feature = "prover")]
+pub fn gen_pk<C: Circuit<Fr>>(
+    params: &ParamsKZG<Bn256>,
+    circuit: &C,
+) -> Result<ProvingKey<G1Affine>, Error> {
+    let vk = keygen_vk(params, circuit)?;
+    Ok(keygen_pk(params, vk, circuit)?)
+}
+
+/// Proves `circuit` against `instances` with the SHPLONK multi-open argument.
+///
+/// With `debug_check` the circuit is first run through `MockProver`, so an unsatisfied
+/// constraint comes back as `Error::MockFailure` naming the failing gates instead of
+/// surfacing later as a proof that does not verify.
+#[cfg(feature = "prover")]
+pub fn prove_fib<C: Circuit<Fr>>(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: C,
+    instances: &[Vec<Fr>],
+    debug_check: bool,
+) -> Result<Vec<u8>, Error> {
+    prove_fib_with::<ProverSHPLONK<'_, Bn256>, _>(params, pk, circuit, instances, debug_check)
+}
+
+/// `prove_fib` over any KZG multi-open prover, e.g. `ProverSHPLONK` or `ProverGWC`.
+///
+/// For `FibCircuit` SHPLONK gives the smaller proof: GWC writes one opening commitment per
+/// distinct evaluation point (the current, next and last-usable rows), while SHPLONK writes
+/// two commitments however many points are queried. The proof starts with a `ProofHeader`
+/// naming the scheme, so `verify_fib` checks it with the matching verifier.
+#[cfg(feature = "prover")]
+pub fn prove_fib_with<'params, P, C>(
+    params: &'params ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: C,
+    instances: &[Vec<Fr>],
+    debug_check: bool,
+) -> Result<Vec<u8>, Error>
+where
+    P: Prover<'params, KZGCommitmentScheme<Bn256>> + MultiOpenTag,
+    C: Circuit<Fr>,
+{
+    prove_fib_with_transcript::<P, Blake2bWrite<_, _, _>, _>(
+        params,
+        pk,
+        circuit,
+        instances,
+        debug_check,
+    )
+}
+
+/// `prove_fib_with` over any transcript, e.g. `Keccak256Write` for proofs an EVM verifier
+/// will check. The header records the hash, so `verify_fib` reads it back with the same one.
+#[cfg(feature = "prover")]
+pub fn prove_fib_with_transcript<'params, P, T, C>(
+    params: &'params ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: C,
+    instances: &[Vec<Fr>],
+    debug_check: bool,
+) -> Result<Vec<u8>, Error>
+where
+    P: Prover<'params, KZGCommitmentScheme<Bn256>> + MultiOpenTag,
+    T: TranscriptWriterBuffer<Vec<u8>, G1Affine, Challenge255<G1Affine>> + TranscriptTag,
+    C: Circuit<Fr>,
+{
+    // MockProver indexes instance columns without checking how many were supplied
+    if instances.len() != pk.get_vk().cs().num_instance_columns() {
+        return Err(plonk::Error::InvalidInstances.into());
+    }
+    if debug_check {
+        MockProver::run(params.k(), &circuit, instances.to_vec())?
+            .verify()
+            .map_err(Error::MockFailure)?;
+    }
+
+    let instances: Vec<&[Fr]> = instances.iter().map(Vec::as_slice).collect();
+    let header = ProofHeader {
+        scheme: P::SCHEME,
+        transcript: T::HASH,
+    };
+    // The transcript appends to what its writer already holds
+    let mut transcript = T::init(header.to_bytes().to_vec());
+
+    create_proof::<KZGCommitmentScheme<Bn256>, P, Challenge255<G1Affine>, _, T, _>(
+        params,
+        pk,
+        &[circuit],
+        &[&instances[..]],
+        OsRng,
+        &mut transcript,
+    )?;
+
+    Ok(transcript.finalize())
+}
+
+/// Verifies a proof from `prove_fib_with` under whichever multi-open scheme and transcript
+/// its header names
+pub fn verify_fib(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    proof: &[u8],
+) -> Result<(), Error> {
+    let (header, transcript) = ProofHeader::parse(proof)?;
+    match header.scheme {
+        MultiOpen::Shplonk => verify_hashed::<VerifierSHPLONK<'_, Bn256>>(
+            params,
+            vk,
+            instances,
+            header.transcript,
+            transcript,
+        ),
+        MultiOpen::Gwc => verify_hashed::<VerifierGWC<'_, Bn256>>(
+            params,
+            vk,
+            instances,
+            header.transcript,
+            transcript,
+        ),
+    }
+}
+
+/// `verify_fib` pinned to one KZG multi-open verifier; a proof whose header names another
+/// scheme is rejected without being read
+pub fn verify_fib_with<'params, V>(
+    params: &'params ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    proof: &[u8],
+) -> Result<(), Error>
+where
+    V: Verifier<'params, KZGCommitmentScheme<Bn256>> + MultiOpenTag,
+    SingleStrategy<'params, Bn256>: VerificationStrategy<'params, KZGCommitmentScheme<Bn256>, V>,
+{
+    let (header, transcript) = ProofHeader::parse(proof)?;
+    if header.scheme != V::SCHEME {
+        return Err(Error::SchemeMismatch {
+            expected: V::SCHEME,
+            found: header.scheme,
+        });
+    }
+    verify_hashed::<V>(params, vk, instances, header.transcript, transcript)
+}
+
+fn verify_hashed<'params, V>(
+    params: &'params ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    hash: TranscriptHash,
+    proof: &[u8],
+) -> Result<(), Error>
+where
+    V: Verifier<'params, KZGCommitmentScheme<Bn256>>,
+    SingleStrategy<'params, Bn256>: VerificationStrategy<'params, KZGCommitmentScheme<Bn256>, V>,
+{
+    match hash {
+        TranscriptHash::Blake2b => {
+            verify_transcript::<V, Blake2bRead<_, _, _>>(params, vk, instances, proof)
+        }
+        TranscriptHash::Keccak256 => {
+            verify_transcript::<V, Keccak256Read<_, _, _>>(params, vk, instances, proof)
+        }
+    }
+}
+
+fn verify_transcript<'params, 'proof, V, T>(
+    params: &'params ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    proof: &'proof [u8],
+) -> Result<(), Error>
+where
+    V: Verifier<'params, KZGCommitmentScheme<Bn256>>,
+    SingleStrategy<'params, Bn256>: VerificationStrategy<'params, KZGCommitmentScheme<Bn256>, V>,
+    T: TranscriptReadBuffer<&'proof [u8], G1Affine, Challenge255<G1Affine>>,
+{
+    let instances: Vec<&[Fr]> = instances.iter().map(Vec::as_slice).collect();
+    let mut transcript = T::init(proof);
+
+    verify_proof::<
+        KZGCommitmentScheme<Bn256>,
+        V,
+        Challenge255<G1Affine>,
+        T,
+        SingleStrategy<'params, Bn256>,
+    >(
+        params.verifier_params(),
+        vk,
+        SingleStrategy::new(params),
+        &[&instances[..]],
+        &mut transcript,
+    )?;
+    Ok(())
+}
+
+/// `prove_fib` with the proof hex-encoded, for text-only transports
+#[cfg(feature = "prover")]
+pub fn prove_hex<C: Circuit<Fr>>(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: C,
+    instances: &[Vec<Fr>],
+) -> Result<String, Error> {
+    prove_fib(params, pk, circuit, instances, false).map(hex::encode)
+}
+
+/// `prove_fib` with the proof base64-encoded (standard alphabet, padded)
+#[cfg(feature = "prover")]
+pub fn prove_base64<C: Circuit<Fr>>(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: C,
+    instances: &[Vec<Fr>],
+) -> Result<String, Error> {
+    prove_fib(params, pk, circuit, instances, false).map(|proof| BASE64.encode(proof))
+}
+
+pub fn verify_hex(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    proof_hex: &str,
+) -> Result<(), Error> {
+    let proof =
+        hex::decode(proof_hex).map_err(|err| Error::InvalidEncoding(format!("hex: {err}")))?;
+    verify_fib(params, vk, instances, &proof)
+}
+
+pub fn verify_base64(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    proof_base64: &str,
+) -> Result<(), Error> {
+    let proof = BASE64
+        .decode(proof_base64)
+        .map_err(|err| Error::InvalidEncoding(format!("base64: {err}")))?;
+    verify_fib(params, vk, instances, &proof)
+}
+
+/// Verifies a proof from a `FibCircuit` with `expose_output` against the instance implied by
+/// `seeds`, rather than one supplied by the prover.
+///
+/// The output is recomputed off-circuit and a `claimed_output` that disagrees is rejected
+/// before any pairing work, so a valid proof cannot be passed off as vouching for another value.
+pub fn verify_claim(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    fib_size: usize,
+    seeds: (Fr, Fr),
+    claimed_output: Fr,
+    proof: &[u8],
+) -> Result<(), Error> {
+    let (a, b) = seeds;
+    let expected = compute(fib_size, a, b);
+    if claimed_output != expected {
+        return Err(Error::ClaimMismatch {
+            claimed: claimed_output,
+            expected,
+        });
+    }
+
+    verify_fib(params, vk, &[vec![a, b, expected]], proof)
+}
+
+/// Verifies a proof from a `FibCircuit` with `expose_output` and returns the F_n it exposes.
+///
+/// The output is read from the default `[a, b, out]` layout; an instance too short to hold it
+/// is rejected before verifying.
+pub fn verify_and_extract(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    proof: &[u8],
+) -> Result<Fr, Error> {
+    let column = instances.first().map(Vec::as_slice).unwrap_or_default();
+    let output = *column
+        .get(OUTPUT_ROW)
+        .ok_or(Error::InstanceIndexOutOfRange {
+            row: OUTPUT_ROW,
+            len: column.len(),
+        })?;
+
+    verify_fib(params, vk, instances, proof)?;
+    Ok(output)
+}
+
+// How many times `prove_auto` may bump k past the estimate before giving up
+#[cfg(feature = "prover")]
+const MAX_K_BUMPS: u32 = 3;
+
+/// Picks the smallest `k` for the circuit, cuts `params` down to it, and generates keys and
+/// proves in one call. `params` must have at least that many rows.
+#[cfg(feature = "prover")]
+pub fn prove_auto(
+    params: &ParamsKZG<Bn256>,
+    circuit: FibCircuit,
+    instances: Vec<Vec<Fr>>,
+) -> Result<ProofBundle, Error> {
+    let k = min_k_for(circuit.fib_size);
+    prove_auto_from(params, circuit, instances, k)
+}
+
+/// `prove_auto` starting from an explicit `k`. While the circuit does not fit, the attempt is
+/// repeated with `k + 1`, at most `MAX_K_BUMPS` times and never past `params.k()`.
+#[cfg(feature = "prover")]
+pub fn prove_auto_from(
+    params: &ParamsKZG<Bn256>,
+    circuit: FibCircuit,
+    instances: Vec<Vec<Fr>>,
+    mut k: u32,
+) -> Result<ProofBundle, Error> {
+    let mut bumps = 0;
+    loop {
+        match prove_at(params, &circuit, &instances, k) {
+            Err(Error::Halo2(plonk::Error::NotEnoughRowsAvailable { .. }))
+                if bumps < MAX_K_BUMPS && k < params.k() =>
+            {
+                log::warn!(
+                    "fib_size = {} does not fit k = {k}, retrying with k = {}",
+                    circuit.fib_size,
+                    k + 1
+                );
+                k += 1;
+                bumps += 1;
+            }
+            result => {
+                return result.map(|proof| ProofBundle {
+                    version: BUNDLE_VERSION,
+                    k,
+                    spec: circuit.spec(),
+                    instances,
+                    proof,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(feature = "prover")]
+fn prove_at(
+    params: &ParamsKZG<Bn256>,
+    circuit: &FibCircuit,
+    instances: &[Vec<Fr>],
+    k: u32,
+) -> Result<Vec<u8>, Error> {
+    circuit.check_instances(instances)?;
+    let params = params_for_k(params, k)?;
+    let pk = gen_pk(&params, circuit)?;
+    prove_fib(&params, &pk, circuit.clone(), instances, false)
+}
+
+/// Proves independent `(circuit, instances)` jobs concurrently on the rayon pool.
+///
+/// All jobs share `params`, cut down to fit the largest circuit, and are proved with that
+/// `k`. Keys and transcripts are per job, since every `fib_size` gives a different circuit.
+/// Bundles come back in job order.
+#[cfg(feature = "prover")]
+pub fn prove_many(
+    params: &ParamsKZG<Bn256>,
+    jobs: &[(FibCircuit, Vec<Vec<Fr>>)],
+) -> Result<Vec<ProofBundle>, Error> {
+    let k = jobs
+        .iter()
+        .map(|(circuit, _)| min_k_for(circuit.fib_size))
+        .max()
+        .unwrap_or(1);
+    let params = params_for_k(params, k)?;
+
+    jobs.par_iter()
+        .map(|(circuit, instances)| {
+            circuit.check_instances(instances)?;
+            let pk = gen_pk(&params, circuit)?;
+            let proof = prove_fib(&params, &pk, circuit.clone(), instances, false)?;
+            Ok(ProofBundle {
+                version: BUNDLE_VERSION,
+                k,
+                spec: circuit.spec(),
+                instances: instances.clone(),
+                proof,
+            })
+        })
+        .collect()
+}
+
+/// Shared flag for abandoning a `prove_cancellable` job from another thread
+#[cfg(feature = "prover")]
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+#[cfg(feature = "prover")]
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn check(&self) -> Result<(), Error> {
+        if self.is_cancelled() {
+            Err(Error::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// `prove_auto` that gives up with `Error::Cancelled` once `token` is cancelled.
+///
+/// `create_proof` cannot be interrupted, so the token is only checked between the setup,
+/// keygen and proving phases; a cancel that lands mid-phase takes effect when it finishes.
+#[cfg(feature = "prover")]
+pub fn prove_cancellable(
+    params: &ParamsKZG<Bn256>,
+    circuit: FibCircuit,
+    instances: Vec<Vec<Fr>>,
+    token: &CancellationToken,
+) -> Result<ProofBundle, Error> {
+    circuit.check_instances(&instances)?;
+    let k = min_k_for(circuit.fib_size);
+    let spec = circuit.spec();
+
+    token.check()?;
+    let params = params_for_k(params, k)?;
+    token.check()?;
+    let vk = keygen_vk(&params, &circuit)?;
+    token.check()?;
+    let pk = keygen_pk(&params, vk, &circuit)?;
+    token.check()?;
+    let proof = prove_fib(&params, &pk, circuit, &instances, false)?;
+
+    Ok(ProofBundle {
+        version: BUNDLE_VERSION,
+        k,
+        spec,
+        instances,
+        proof,
+    })
+}
+
+/// The `k` and number of instance columns `vk` was generated for
+pub fn vk_shape(vk: &VerifyingKey<G1Affine>) -> (u32, usize) {
+    (vk.get_domain().k(), vk.cs().num_instance_columns())
+}
+
+pub fn vk_matches(vk: &VerifyingKey<G1Affine>, k: u32, num_instance: usize) -> bool {
+    vk_shape(vk) == (k, num_instance)
+}
+
+/// Checks the bundle's `k` against the params and its shape against `vk`, both before any
+/// pairing work, then verifies the proof
+pub fn verify_bundle(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    bundle: &ProofBundle,
+) -> Result<(), Error> {
+    if params.k() != bundle.k {
+        return Err(Error::KMismatch {
+            expected: params.k(),
+            found: bundle.k,
+        });
+    }
+    if !vk_matches(vk, bundle.k, bundle.instances.len()) {
+        let (vk_k, vk_instances) = vk_shape(vk);
+        return Err(Error::VkMismatch {
+            vk_k,
+            vk_instances,
+            k: bundle.k,
+            instances: bundle.instances.len(),
+        });
+    }
+    verify_fib(params, vk, &bundle.instances, &bundle.proof)
+}
+
+/// Verifies `bundles` one after another against the same key, stopping at the first failure
+/// and reporting its index
+pub fn verify_all(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    bundles: &[ProofBundle],
+) -> Result<(), Error> {
+    for (index, bundle) in bundles.iter().enumerate() {
+        verify_bundle(params, vk, bundle).map_err(|err| Error::BundleFailed {
+            index,
+            source: Box::new(err),
+        })?;
+    }
+    Ok(())
+}
+
+/// `verify_bundle` that first rejects a bundle whose spec differs from `expected_spec`, the
+/// spec `vk` was generated for, so a proof for another circuit version is never checked
+/// against the wrong key
+pub fn verify_bundle_checked(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    bundle: &ProofBundle,
+    expected_spec: &CircuitSpec,
+) -> Result<(), Error> {
+    if &bundle.spec != expected_spec {
+        return Err(Error::SpecMismatch {
+            expected: expected_spec.clone(),
+            found: bundle.spec.clone(),
+        });
+    }
+    verify_bundle(params, vk, bundle)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::SumCircuit;
+    use crate::InstanceLayout;
+
+    #[test]
+    fn min_k_covers_blinding_rows() {
+        // 998 assigned rows plus the reserved ones still fit 2^10
+        let cs = constraint_system();
+        assert!(rows_required(1000) + cs.blinding_factors() + 1 <= 1 << 10);
+        assert_eq!(min_k_for(1000), 10);
+        assert_eq!(
+            min_k_for(3),
+            cs.minimum_rows().next_power_of_two().trailing_zeros()
+        );
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let k = min_k_for(circuit.fib_size);
+
+        let params = gen_params(k);
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+
+        verify_fib(&params, pk.get_vk(), &instances, &proof).unwrap();
+    }
+
+    #[test]
+    fn debug_check_reports_mock_failure() {
+        let circuit = SumCircuit {
+            n: 10,
+            check_identity: false,
+        };
+        let params = gen_params(5);
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        // The sum of the first ten terms is 143, not 142
+        let instances = vec![vec![Fr::from(1), Fr::from(1), Fr::from(142)]];
+        let result = prove_fib(&params, &pk, circuit, &instances, true);
+
+        assert!(matches!(result, Err(Error::MockFailure(_))));
+    }
+
+    #[test]
+    fn verify_claim_binds_output() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let seeds = (Fr::from(1), Fr::from(1));
+        let instances = vec![vec![seeds.0, seeds.1, Fr::from(55)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+
+        verify_claim(&params, pk.get_vk(), 10, seeds, Fr::from(55), &proof).unwrap();
+
+        let result = verify_claim(&params, pk.get_vk(), 10, seeds, Fr::from(56), &proof);
+        assert!(matches!(result, Err(Error::ClaimMismatch { .. })));
+    }
+
+    #[test]
+    fn verify_returns_output() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let instances = vec![vec![Fr::from(1), Fr::from(1), Fr::from(55)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+        let output = verify_and_extract(&params, pk.get_vk(), &instances, &proof).unwrap();
+        assert_eq!(output, Fr::from(55));
+
+        let wrong = vec![vec![Fr::from(1), Fr::from(1), Fr::from(56)]];
+        assert!(verify_and_extract(&params, pk.get_vk(), &wrong, &proof).is_err());
+        assert!(matches!(
+            verify_and_extract(&params, pk.get_vk(), &[vec![Fr::from(1)]], &proof),
+            Err(Error::InstanceIndexOutOfRange { row: 2, len: 1 })
+        ));
+    }
+
+    #[test]
+    fn output_first_layout() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            instance_layout: InstanceLayout::OutputFirst,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let instances = vec![vec![Fr::from(55), Fr::from(1), Fr::from(1)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, true).unwrap();
+        verify_fib(&params, pk.get_vk(), &instances, &proof).unwrap();
+
+        // The same values in seeds-first order no longer match the key
+        let seeds_first = vec![vec![Fr::from(1), Fr::from(1), Fr::from(55)]];
+        assert!(verify_fib(&params, pk.get_vk(), &seeds_first, &proof).is_err());
+    }
+
+    #[test]
+    fn malformed_inputs_error_cleanly() {
+        for fib_size in [0, 1, 2] {
+            let circuit = FibCircuit {
+                fib_size,
+                ..Default::default()
+            };
+            let instance = vec![vec![Fr::from(1), Fr::from(1)]];
+            assert!(MockProver::run(4, &circuit, instance).is_err());
+        }
+        assert_eq!(min_k_for(usize::MAX), usize::BITS);
+
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        for instances in [vec![], vec![vec![Fr::from(1)]; 2]] {
+            let result = prove_fib(&params, &pk, circuit.clone(), &instances, true);
+            assert!(matches!(
+                result,
+                Err(Error::Halo2(plonk::Error::InvalidInstances))
+            ));
+        }
+
+        // Longer than the usable rows of a k = 4 circuit
+        let instances = vec![vec![Fr::from(1); 16]];
+        assert!(prove_fib(&params, &pk, circuit.clone(), &instances, true).is_err());
+
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+        assert!(verify_fib(&params, pk.get_vk(), &[], &proof).is_err());
+        assert!(verify_fib(&params, pk.get_vk(), &instances, &proof[..proof.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn prove_auto_sizes() {
+        let setup = gen_params(min_k_for(500) + 1);
+        for fib_size in [5, 50, 500] {
+            let circuit = FibCircuit {
+                fib_size,
+                expose_output: true,
+                ..Default::default()
+            };
+            let out = compute(fib_size, Fr::from(1), Fr::from(1));
+            let instances = vec![vec![Fr::from(1), Fr::from(1), out]];
+
+            let bundle = prove_auto(&setup, circuit.clone(), instances).unwrap();
+            assert_eq!(bundle.k, min_k_for(fib_size));
+
+            let params = params_for_k(&setup, bundle.k).unwrap();
+            let vk = keygen_vk(&params, &circuit).unwrap();
+            verify_bundle(&params, &vk, &bundle).unwrap();
+
+            let wrong = params_for_k(&setup, bundle.k + 1).unwrap();
+            assert!(matches!(
+                verify_bundle(&wrong, &vk, &bundle),
+                Err(Error::KMismatch { .. })
+            ));
+        }
+    }
+
+    // `FibCircuit` with a second, unused instance column
+    struct TwoInstanceCircuit(FibCircuit);
+
+    impl Circuit<Fr> for TwoInstanceCircuit {
+        type Config = <FibCircuit as Circuit<Fr>>::Config;
+        type FloorPlanner = <FibCircuit as Circuit<Fr>>::FloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0.clone())
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let config = <FibCircuit as Circuit<Fr>>::configure(cs);
+            cs.instance_column();
+            config
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            layouter: impl halo2_base::halo2_proofs::circuit::Layouter<Fr>,
+        ) -> Result<(), plonk::Error> {
+            self.0.synthesize(config, layouter)
+        }
+    }
+
+    // `FibCircuit` laid out by the V1 floor planner, which may reorder its regions
+    struct V1Circuit(FibCircuit);
+
+    impl Circuit<Fr> for V1Circuit {
+        type Config = <FibCircuit as Circuit<Fr>>::Config;
+        type FloorPlanner = halo2_base::halo2_proofs::circuit::floor_planner::V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0.clone())
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+            <FibCircuit as Circuit<Fr>>::configure(cs)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            layouter: impl halo2_base::halo2_proofs::circuit::Layouter<Fr>,
+        ) -> Result<(), plonk::Error> {
+            self.0.synthesize(config, layouter)
+        }
+    }
+
+    #[test]
+    fn v1_planner_keeps_instance_rows() {
+        let circuit = V1Circuit(FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        });
+        let params = gen_params(min_k_for(circuit.0.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        // Distinct seeds, so reading instance rows 0 and 1 swapped or shifted changes F_10
+        let (a, b) = (Fr::from(2), Fr::from(5));
+        let expected = compute(10, a, b);
+        assert_ne!(expected, compute(10, b, a));
+
+        let instances = vec![vec![a, b, expected]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, true).unwrap();
+        let output = verify_and_extract(&params, pk.get_vk(), &instances, &proof).unwrap();
+        assert_eq!(output, expected);
+
+        let swapped = vec![vec![b, a, expected]];
+        assert!(verify_and_extract(&params, pk.get_vk(), &swapped, &proof).is_err());
+    }
+
+    #[test]
+    fn vk_shape_is_checked_first() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let bundle = prove_auto(
+            &params,
+            circuit.clone(),
+            vec![vec![Fr::from(1), Fr::from(1)]],
+        );
+        let bundle = bundle.unwrap();
+
+        let vk = keygen_vk(&params, &TwoInstanceCircuit(circuit)).unwrap();
+        assert_eq!(vk_shape(&vk), (bundle.k, 2));
+        assert!(!vk_matches(&vk, bundle.k, 1));
+
+        assert!(matches!(
+            verify_bundle(&params, &vk, &bundle),
+            Err(Error::VkMismatch {
+                vk_instances: 2,
+                instances: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn text_encoded_proofs() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+
+        let proof = prove_hex(&params, &pk, circuit.clone(), &instances).unwrap();
+        verify_hex(&params, pk.get_vk(), &instances, &proof).unwrap();
+        assert!(matches!(
+            verify_hex(&params, pk.get_vk(), &instances, "not hex"),
+            Err(Error::InvalidEncoding(_))
+        ));
+
+        let proof = prove_base64(&params, &pk, circuit, &instances).unwrap();
+        verify_base64(&params, pk.get_vk(), &instances, &proof).unwrap();
+        assert!(matches!(
+            verify_base64(&params, pk.get_vk(), &instances, "!!"),
+            Err(Error::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn prove_auto_retries_small_k() {
+        let circuit = FibCircuit {
+            fib_size: 500,
+            ..Default::default()
+        };
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        assert_eq!(min_k_for(circuit.fib_size), 9);
+
+        let setup = gen_params(10);
+        let bundle = prove_auto_from(&setup, circuit.clone(), instances.clone(), 7).unwrap();
+        assert_eq!(bundle.k, 9);
+
+        let params = params_for_k(&setup, bundle.k).unwrap();
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        verify_bundle(&params, &vk, &bundle).unwrap();
+
+        // Three bumps from k = 4 still leave it short
+        assert!(matches!(
+            prove_auto_from(&setup, circuit.clone(), instances.clone(), 4),
+            Err(Error::Halo2(plonk::Error::NotEnoughRowsAvailable { .. }))
+        ));
+        // And no bump goes past the rows the caller's params have
+        assert!(matches!(
+            prove_auto_from(&params_for_k(&setup, 8).unwrap(), circuit, instances, 7),
+            Err(Error::Halo2(plonk::Error::NotEnoughRowsAvailable {
+                current_k: 8
+            }))
+        ));
+    }
+
+    #[test]
+    fn multiopen_schemes() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+
+        let shplonk = prove_fib_with::<ProverSHPLONK<'_, Bn256>, _>(
+            &params,
+            &pk,
+            circuit.clone(),
+            &instances,
+            false,
+        )
+        .unwrap();
+        let gwc =
+            prove_fib_with::<ProverGWC<'_, Bn256>, _>(&params, &pk, circuit, &instances, false)
+                .unwrap();
+
+        verify_fib_with::<VerifierSHPLONK<'_, Bn256>>(&params, pk.get_vk(), &instances, &shplonk)
+            .unwrap();
+        verify_fib_with::<VerifierGWC<'_, Bn256>>(&params, pk.get_vk(), &instances, &gwc).unwrap();
+        assert!(matches!(
+            verify_fib_with::<VerifierGWC<'_, Bn256>>(&params, pk.get_vk(), &instances, &shplonk),
+            Err(Error::SchemeMismatch { .. })
+        ));
+
+        // Three opening points cost GWC one more commitment than SHPLONK's fixed two
+        assert!(shplonk.len() < gwc.len());
+    }
+
+    #[test]
+    fn header_selects_scheme() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+
+        let shplonk = prove_fib_with::<ProverSHPLONK<'_, Bn256>, _>(
+            &params,
+            &pk,
+            circuit.clone(),
+            &instances,
+            false,
+        )
+        .unwrap();
+        let gwc =
+            prove_fib_with::<ProverGWC<'_, Bn256>, _>(&params, &pk, circuit, &instances, false)
+                .unwrap();
+
+        let (header, _) = ProofHeader::parse(&shplonk).unwrap();
+        assert_eq!(header.scheme, MultiOpen::Shplonk);
+        assert_eq!(header.transcript, TranscriptHash::Blake2b);
+        verify_fib(&params, pk.get_vk(), &instances, &shplonk).unwrap();
+        verify_fib(&params, pk.get_vk(), &instances, &gwc).unwrap();
+
+        // Relabelling the scheme sends the transcript to the wrong verifier
+        let mut relabelled = shplonk.clone();
+        relabelled[1] = MultiOpen::Gwc as u8;
+        assert!(verify_fib(&params, pk.get_vk(), &instances, &relabelled).is_err());
+
+        let mut future = shplonk;
+        future[0] = PROOF_FORMAT_VERSION + 1;
+        assert!(matches!(
+            verify_fib(&params, pk.get_vk(), &instances, &future),
+            Err(Error::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn keccak_transcript() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+
+        let proof = prove_fib_with_transcript::<
+            ProverSHPLONK<'_, Bn256>,
+            Keccak256Write<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+            _,
+        >(&params, &pk, circuit.clone(), &instances, false)
+        .unwrap();
+        let (header, _) = ProofHeader::parse(&proof).unwrap();
+        assert_eq!(header.transcript, TranscriptHash::Keccak256);
+        verify_fib(&params, pk.get_vk(), &instances, &proof).unwrap();
+        verify_fib_with::<VerifierSHPLONK<'_, Bn256>>(&params, pk.get_vk(), &instances, &proof)
+            .unwrap();
+
+        // Read back under Blake2b the challenges come out different
+        let mut relabelled = proof.clone();
+        relabelled[2] = TranscriptHash::Blake2b as u8;
+        assert!(verify_fib(&params, pk.get_vk(), &instances, &relabelled).is_err());
+
+        // Same layout, only the hash differs
+        let blake = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+        assert_eq!(blake.len(), proof.len());
+    }
+
+    #[test]
+    #[ignore]
+    fn multiopen_timing() {
+        let circuit = FibCircuit {
+            fib_size: 1 << 12,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+
+        let start = std::time::Instant::now();
+        let shplonk = prove_fib_with::<ProverSHPLONK<'_, Bn256>, _>(
+            &params,
+            &pk,
+            circuit.clone(),
+            &instances,
+            false,
+        )
+        .unwrap();
+        let shplonk_prove = start.elapsed();
+        let start = std::time::Instant::now();
+        verify_fib_with::<VerifierSHPLONK<'_, Bn256>>(&params, pk.get_vk(), &instances, &shplonk)
+            .unwrap();
+        let shplonk_verify = start.elapsed();
+
+        let start = std::time::Instant::now();
+        let gwc =
+            prove_fib_with::<ProverGWC<'_, Bn256>, _>(&params, &pk, circuit, &instances, false)
+                .unwrap();
+        let gwc_prove = start.elapsed();
+        let start = std::time::Instant::now();
+        verify_fib_with::<VerifierGWC<'_, Bn256>>(&params, pk.get_vk(), &instances, &gwc).unwrap();
+        let gwc_verify = start.elapsed();
+
+        println!(
+            "shplonk: {} bytes, prove {shplonk_prove:?}, verify {shplonk_verify:?}",
+            shplonk.len()
+        );
+        println!(
+            "gwc: {} bytes, prove {gwc_prove:?}, verify {gwc_verify:?}",
+            gwc.len()
+        );
+    }
+
+    #[test]
+    fn keygen_from_without_witnesses() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+        let pk = keygen_pk(&params, vk.clone(), &circuit).unwrap();
+
+        let instances = vec![vec![Fr::from(1), Fr::from(1), Fr::from(55)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+        verify_fib(&params, &vk, &instances, &proof).unwrap();
+    }
+
+    #[test]
+    fn cancel_before_proving() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let token = CancellationToken::new();
+        let bundle = prove_cancellable(&params, circuit.clone(), instances.clone(), &token);
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        verify_bundle(&params, &vk, &bundle.unwrap()).unwrap();
+
+        // A clone shares the flag, as a handle held by the connection would
+        token.clone().cancel();
+        assert!(matches!(
+            prove_cancellable(&params, circuit, instances, &token),
+            Err(Error::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn bundle_spec_is_checked() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let instances = vec![vec![Fr::from(1), Fr::from(1), Fr::from(55)]];
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let bundle = prove_auto(&params, circuit.clone(), instances).unwrap();
+
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        verify_bundle_checked(&params, &vk, &bundle, &circuit.spec()).unwrap();
+
+        let stale = CircuitSpec {
+            version: crate::spec::SPEC_VERSION + 1,
+            ..circuit.spec()
+        };
+        assert!(matches!(
+            verify_bundle_checked(&params, &vk, &bundle, &stale),
+            Err(Error::SpecMismatch { .. })
+        ));
+        assert!(matches!(
+            verify_bundle_checked(&params, &vk, &bundle, &CircuitSpec::fibonacci(10)),
+            Err(Error::SpecMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn bundle_version_is_checked() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let bundle = prove_auto(&params, circuit, vec![vec![Fr::from(1), Fr::from(1)]]).unwrap();
+
+        let bytes = bundle.to_bytes().unwrap();
+        let decoded = ProofBundle::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.version, BUNDLE_VERSION);
+        assert_eq!(decoded.spec, bundle.spec);
+        assert_eq!(decoded.instances, bundle.instances);
+        assert_eq!(decoded.proof, bundle.proof);
+
+        let newer = ProofBundle {
+            version: BUNDLE_VERSION + 1,
+            ..bundle
+        };
+        assert!(matches!(
+            ProofBundle::from_bytes(&newer.to_bytes().unwrap()),
+            Err(Error::UnsupportedBundleVersion { found, .. }) if found == BUNDLE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn verify_all_reports_failing_index() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let mut bundles: Vec<_> = [(1u64, 1u64), (2, 3)]
+            .into_iter()
+            .map(|(a, b)| {
+                let instances = vec![vec![Fr::from(a), Fr::from(b)]];
+                prove_auto(&params, circuit.clone(), instances).unwrap()
+            })
+            .collect();
+
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        verify_all(&params, &vk, &bundles).unwrap();
+
+        let last = bundles[1].proof.len() - 1;
+        bundles[1].proof[last] ^= 1;
+        assert!(matches!(
+            verify_all(&params, &vk, &bundles),
+            Err(Error::BundleFailed { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn prove_many_in_parallel() {
+        let jobs: Vec<_> = [(10, 1u64, 1u64), (20, 2, 3), (100, 1, 1), (300, 5, 8)]
+            .into_iter()
+            .map(|(fib_size, a, b)| {
+                let circuit = FibCircuit {
+                    fib_size,
+                    expose_output: true,
+                    ..Default::default()
+                };
+                let (a, b) = (Fr::from(a), Fr::from(b));
+                (circuit, vec![vec![a, b, compute(fib_size, a, b)]])
+            })
+            .collect();
+
+        let params = gen_params(min_k_for(300));
+        let bundles = prove_many(&params, &jobs).unwrap();
+        assert_eq!(bundles.len(), jobs.len());
+
+        for ((circuit, instances), bundle) in jobs.iter().zip(&bundles) {
+            assert_eq!(&bundle.instances, instances);
+            let vk = keygen_vk(&params, circuit).unwrap();
+            verify_bundle_checked(&params, &vk, bundle, &circuit.spec()).unwrap();
+        }
+    }
+
+    #[test]
+    fn out_of_range_instance_row() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+
+        // The output is exposed at row 2, but the instance stops after the seeds
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        assert!(matches!(
+            prove_auto(&gen_params(min_k_for(10)), circuit.clone(), instances),
+            Err(Error::InstanceIndexOutOfRange { row: 2, len: 2 })
+        ));
+        assert!(matches!(
+            circuit.check_instances(&[]),
+            Err(Error::InstanceIndexOutOfRange { row: 0, len: 0 })
+        ));
+    }
+
+    // Verification work depends on k and the circuit's columns and gates, never on how many
+    // rows are used, so a 1000x longer sequence under the same k verifies in the same time
+    #[test]
+    #[ignore]
+    fn verify_time_independent_of_fib_size() {
+        const RUNS: usize = 20;
+
+        let params = gen_params(min_k_for(100_000));
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+
+        let medians: Vec<_> = [100, 100_000]
+            .into_iter()
+            .map(|fib_size| {
+                let circuit = FibCircuit {
+                    fib_size,
+                    ..Default::default()
+                };
+                let pk = gen_pk(&params, &circuit).unwrap();
+                let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+
+                let median = median_elapsed(RUNS, || {
+                    verify_fib(&params, pk.get_vk(), &instances, &proof).unwrap();
+                });
+                println!("fib_size = {fib_size}: median verify {median:?}");
+                median
+            })
+            .collect();
+
+        let ratio = medians[1].as_secs_f64() / medians[0].as_secs_f64();
+        assert!((0.67..1.5).contains(&ratio), "verify time ratio {ratio:.2}");
+    }
+
+    fn median_elapsed(runs: usize, mut f: impl FnMut()) -> std::time::Duration {
+        let mut times: Vec<_> = (0..runs)
+            .map(|_| {
+                let start = std::time::Instant::now();
+                f();
+                start.elapsed()
+            })
+            .collect();
+        times.sort();
+        times[runs / 2]
+    }
+
+    // Keygen is dominated by FFTs over the 2^k domain, so two steps of k should cost about
+    // 4 (k + 2) / k times as much. A factor of two either way allows for fixed overhead at the
+    // small end while still catching anything quadratic.
+    #[test]
+    #[ignore]
+    fn keygen_scales_with_k() {
+        const RUNS: usize = 5;
+
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let ks = [10u32, 12, 14];
+        let medians: Vec<_> = ks
+            .iter()
+            .map(|&k| {
+                let params = gen_params(k);
+                let median = median_elapsed(RUNS, || {
+                    gen_pk(&params, &circuit).unwrap();
+                });
+                println!("k = {k}: median keygen {median:?}");
+                median
+            })
+            .collect();
+
+        for (pair, times) in ks.windows(2).zip(medians.windows(2)) {
+            let expected = 4.0 * pair[1] as f64 / pair[0] as f64;
+            let ratio = times[1].as_secs_f64() / times[0].as_secs_f64();
+            assert!(
+                (expected / 2.0..expected * 2.0).contains(&ratio),
+                "keygen k = {} -> {}: ratio {ratio:.2}, expected about {expected:.2}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    // The crate's headline size under the real prover; run with `--include-ignored`
+    #[test]
+    #[ignore]
+    fn flagship_size_real_proof() {
+        let circuit = FibCircuit {
+            fib_size: 1_000_000,
+            expose_output: true,
+            ..Default::default()
+        };
+        let k = min_k_for(circuit.fib_size);
+        assert_eq!(k, 20);
+
+        let params = gen_params(k);
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let out = compute(circuit.fib_size, Fr::from(1), Fr::from(1));
+        let instances = vec![vec![Fr::from(1), Fr::from(1), out]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+
+        verify_fib(&params, pk.get_vk(), &instances, &proof).unwrap();
+    }
+}
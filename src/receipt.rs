@@ -0,0 +1,137 @@
+use crate::circuit::{OUTPUT_ROW, PRIVATE_OUTPUT_ROW, SEED_ROWS};
+use crate::error::Error;
+use crate::prover::ProofBundle;
+use crate::spec::SequenceKind;
+use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Human-readable summary of what a `ProofBundle` proves, for audit logs.
+///
+/// Nothing here is checked by a verifier: field elements are rendered as hex strings and the
+/// timestamp is when the receipt was written, not when the proof was made.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProofReceipt {
+    pub sequence: SequenceKind,
+    pub fib_size: usize,
+    /// The seeds, when the instance carries them
+    pub seeds: Option<[String; 2]>,
+    /// The exposed F_n, when the spec exposes one
+    pub output: Option<String>,
+    pub k: u32,
+    pub proof_size: usize,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl ProofReceipt {
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| Error::InvalidEncoding(format!("receipt: {err}")))
+    }
+}
+
+impl ProofBundle {
+    /// Summarizes the bundle, reading seeds and output from the instance rows the spec's
+    /// layout maps them to. An instance too short for both seeds comes from a
+    /// `WitnessSource` circuit: its seeds are private and its output, if any, moves up to the
+    /// first logical row.
+    pub fn receipt(&self) -> ProofReceipt {
+        let column = self.instances.first().map(Vec::as_slice).unwrap_or(&[]);
+        let layout = &self.spec.instance_layout;
+        let cell = |row: usize| {
+            column
+                .get(layout.row(row))
+                .map(|value: &Fr| format!("{value:?}"))
+        };
+
+        let private_seeds = self.spec.instance_len < SEED_ROWS.len();
+        let seeds = if private_seeds {
+            None
+        } else {
+            let [a, b] = SEED_ROWS.map(cell);
+            a.zip(b).map(|(a, b)| [a, b])
+        };
+        let output = match (private_seeds, self.spec.instance_len) {
+            (true, 1) => cell(PRIVATE_OUTPUT_ROW),
+            (false, len) if len > SEED_ROWS.len() => cell(OUTPUT_ROW),
+            _ => None,
+        };
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+
+        ProofReceipt {
+            sequence: self.spec.sequence,
+            fib_size: self.spec.fib_size,
+            seeds,
+            output,
+            k: self.k,
+            proof_size: self.proof.len(),
+            timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::FibCircuit;
+    use crate::prover::{gen_params, min_k_for, prove_auto};
+    use crate::InstanceLayout;
+
+    #[test]
+    fn receipt_matches_circuit() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let instances = vec![vec![Fr::from(1), Fr::from(1), Fr::from(55)]];
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let bundle = prove_auto(&params, circuit, instances).unwrap();
+
+        let receipt = bundle.receipt();
+        assert_eq!(receipt.sequence, SequenceKind::Fibonacci);
+        assert_eq!(receipt.fib_size, 10);
+        assert_eq!(
+            receipt.seeds,
+            Some([format!("{:?}", Fr::from(1)), format!("{:?}", Fr::from(1))])
+        );
+        assert_eq!(receipt.output, Some(format!("{:?}", Fr::from(55))));
+        assert_eq!(receipt.k, bundle.k);
+        assert_eq!(receipt.proof_size, bundle.proof.len());
+        assert!(receipt.timestamp > 0);
+
+        let json: serde_json::Value = serde_json::from_str(&receipt.to_json().unwrap()).unwrap();
+        assert_eq!(json["fib_size"], 10);
+        assert_eq!(json["sequence"], "Fibonacci");
+
+        // Under `OutputFirst` the same values sit at [55, 1, 1]
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            instance_layout: InstanceLayout::OutputFirst,
+            ..Default::default()
+        };
+        let instances = vec![vec![Fr::from(55), Fr::from(1), Fr::from(1)]];
+        let bundle = prove_auto(&params, circuit, instances).unwrap();
+
+        let receipt = bundle.receipt();
+        assert_eq!(
+            receipt.seeds,
+            Some([format!("{:?}", Fr::from(1)), format!("{:?}", Fr::from(1))])
+        );
+        assert_eq!(receipt.output, Some(format!("{:?}", Fr::from(55))));
+
+        // An instance holding only the output has private seeds
+        let mut bundle = bundle;
+        bundle.spec.instance_len = 1;
+        bundle.spec.instance_layout = InstanceLayout::default();
+        bundle.instances = vec![vec![Fr::from(55)]];
+        let receipt = bundle.receipt();
+        assert_eq!(receipt.seeds, None);
+        assert_eq!(receipt.output, Some(format!("{:?}", Fr::from(55))));
+    }
+}
@@ -0,0 +1,168 @@
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [start.0, start.1, end.0, end.1]
+const END_ROW: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct RollupConfig {
+    /// The state (F_i, F_{i+1}), one row per state
+    pub state: [Column<Advice>; 2],
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// Advances a (F_i, F_{i+1}) state by a batch of transitions, publishing only the first and
+/// last states. Every interior row is tied to its neighbour by the transition gate.
+pub struct RollupFibChip<F: FieldExt> {
+    config: RollupConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> RollupFibChip<F> {
+    pub fn construct(config: RollupConfig) -> RollupFibChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        state: [Column<Advice>; 2],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> RollupConfig {
+        let [col_x, col_y] = state;
+        let selector = cs.selector();
+
+        cs.enable_equality(col_x);
+        cs.enable_equality(col_y);
+        cs.enable_equality(instance);
+
+        // (x, y) -> (y, x + y)
+        cs.create_gate("transition", |cells| {
+            let s = cells.query_selector(selector);
+            let x = cells.query_advice(col_x, Rotation::cur());
+            let y = cells.query_advice(col_y, Rotation::cur());
+            let x_next = cells.query_advice(col_x, Rotation::next());
+            let y_next = cells.query_advice(col_y, Rotation::next());
+
+            vec![s.clone() * (x_next - y.clone()), s * (y_next - x - y)]
+        });
+
+        RollupConfig {
+            state,
+            selector,
+            instance,
+        }
+    }
+
+    /// Applies `steps` transitions to the state read from the instance and returns the final
+    /// state's cells
+    pub fn assign_batch(
+        &self,
+        mut layouter: impl Layouter<F>,
+        steps: usize,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), Error> {
+        let [col_x, col_y] = self.config.state;
+
+        layouter.assign_region(
+            || "batch",
+            |mut region| {
+                let mut x = region.assign_advice_from_instance(
+                    || "x",
+                    self.config.instance,
+                    0,
+                    col_x,
+                    0,
+                )?;
+                let mut y = region.assign_advice_from_instance(
+                    || "y",
+                    self.config.instance,
+                    1,
+                    col_y,
+                    0,
+                )?;
+
+                for row in 1..=steps {
+                    // The gate sits on the row being advanced from
+                    self.config.selector.enable(&mut region, row - 1)?;
+
+                    let next_y = x.value().copied() + y.value().copied();
+                    let next_x = y.value().copied();
+                    x = region.assign_advice(|| "x", col_x, row, || next_x)?;
+                    y = region.assign_advice(|| "y", col_y, row, || next_y)?;
+                }
+
+                Ok((x, y))
+            },
+        )
+    }
+
+    pub fn expose_end(
+        &self,
+        mut layouter: impl Layouter<F>,
+        end: &(AssignedCell<F, F>, AssignedCell<F, F>),
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(end.0.cell(), self.config.instance, END_ROW)?;
+        layouter.constrain_instance(end.1.cell(), self.config.instance, END_ROW + 1)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RollupCircuit {
+    pub steps: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for RollupCircuit {
+    type Config = RollupConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let state = [cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        RollupFibChip::configure(state, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = RollupFibChip::construct(config);
+
+        let end = chip.assign_batch(layouter.namespace(|| "batch"), self.steps)?;
+        chip.expose_end(layouter.namespace(|| "end"), &end)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::compute;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn rollup_batch() {
+        let one = Fr::from(1);
+        let f_100 = compute(100, one, one);
+        let f_101 = compute(101, one, one);
+
+        // (F_1, F_2) = (1, 1) reaches (F_100, F_101) after 99 transitions
+        let circuit = RollupCircuit { steps: 99 };
+        let instance = vec![one, one, f_100, f_101];
+        let prover = MockProver::<Fr>::run(7, &circuit, vec![instance]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let instance = vec![one, one, f_101, f_100];
+        let prover = MockProver::<Fr>::run(7, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::circuit::FibCircuit;
+use crate::error::Error;
+use crate::prover::{gen_pk, min_k_for, params_for_k, prove_fib, ProofBundle, BUNDLE_VERSION};
+use crate::spec::CircuitSpec;
+use halo2_base::halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::ProvingKey,
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+    SerdeFormat,
+};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Params, proving key and spec for one circuit shape, kept together so a worker can prove
+/// any number of circuits of that shape after a single load.
+///
+/// The file is the spec as length-prefixed JSON, then the params, then the proving key.
+pub struct ProvingSession {
+    pub params: ParamsKZG<Bn256>,
+    pub pk: ProvingKey<G1Affine>,
+    pub spec: CircuitSpec,
+}
+
+impl ProvingSession {
+    /// Runs keygen for `spec` over `params`, cut down to the smallest `k` the spec fits
+    pub fn new(params: &ParamsKZG<Bn256>, spec: CircuitSpec) -> Result<Self, Error> {
+        let circuit = FibCircuit::from_spec(&spec)?;
+        let params = params_for_k(params, min_k_for(spec.fib_size))?;
+        let pk = gen_pk(&params, &circuit)?;
+
+        Ok(Self { params, pk, spec })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let spec = serde_json::to_vec(&self.spec)
+            .map_err(|err| Error::InvalidEncoding(format!("spec: {err}")))?;
+        writer.write_all(&(spec.len() as u32).to_le_bytes())?;
+        writer.write_all(&spec)?;
+        self.params.write(&mut writer)?;
+        self.pk.write(&mut writer, SerdeFormat::RawBytes)?;
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let mut spec = vec![0u8; u32::from_le_bytes(len) as usize];
+        reader.read_exact(&mut spec)?;
+        let spec: CircuitSpec = serde_json::from_slice(&spec)
+            .map_err(|err| Error::InvalidEncoding(format!("spec: {err}")))?;
+
+        let params = ParamsKZG::<Bn256>::read(&mut reader)?;
+        let pk = ProvingKey::read::<_, FibCircuit>(&mut reader, SerdeFormat::RawBytes)?;
+
+        Ok(Self { params, pk, spec })
+    }
+
+    /// Proves `circuit`, which must have the session's spec
+    pub fn prove(
+        &self,
+        circuit: FibCircuit,
+        instances: Vec<Vec<Fr>>,
+    ) -> Result<ProofBundle, Error> {
+        let spec = circuit.spec();
+        if spec != self.spec {
+            return Err(Error::SpecMismatch {
+                expected: self.spec.clone(),
+                found: spec,
+            });
+        }
+        circuit.check_instances(&instances)?;
+
+        let proof = prove_fib(&self.params, &self.pk, circuit, &instances, false)?;
+        Ok(ProofBundle {
+            version: BUNDLE_VERSION,
+            k: self.params.k(),
+            spec,
+            instances,
+            proof,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::compute;
+    use crate::prover::{gen_params, verify_bundle_checked};
+    use crate::InstanceLayout;
+
+    #[test]
+    fn saved_session_proves() {
+        let spec = CircuitSpec {
+            instance_len: 3,
+            ..CircuitSpec::fibonacci(10)
+        };
+        let path = std::env::temp_dir().join(format!("gpu-test-session-{}", std::process::id()));
+        ProvingSession::new(&gen_params(min_k_for(spec.fib_size)), spec.clone())
+            .unwrap()
+            .save(&path)
+            .unwrap();
+
+        let session = ProvingSession::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(session.spec, spec);
+
+        for (a, b) in [(1u64, 1), (2, 3)] {
+            let (a, b) = (Fr::from(a), Fr::from(b));
+            let circuit = FibCircuit::from_spec(&spec).unwrap();
+            let instances = vec![vec![a, b, compute(10, a, b)]];
+
+            let bundle = session.prove(circuit, instances).unwrap();
+            verify_bundle_checked(&session.params, session.pk.get_vk(), &bundle, &spec).unwrap();
+        }
+
+        let other = FibCircuit {
+            fib_size: 11,
+            expose_output: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            session.prove(other, vec![vec![Fr::from(1); 3]]),
+            Err(Error::SpecMismatch { .. })
+        ));
+
+        // Same size, but the key's copy constraints expect the seeds first
+        let reordered = FibCircuit {
+            instance_layout: InstanceLayout::OutputFirst,
+            ..FibCircuit::from_spec(&spec).unwrap()
+        };
+        assert!(matches!(
+            session.prove(reordered, vec![vec![Fr::from(1); 3]]),
+            Err(Error::SpecMismatch { .. })
+        ));
+    }
+}
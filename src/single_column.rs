@@ -0,0 +1,179 @@
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [F_1, F_2, F_n], matching `FibCircuit` with `expose_output`
+const OUT_ROW: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct SingleColumnConfig {
+    /// F_{i+1} on row i
+    pub column: Column<Advice>,
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// The whole sequence down one advice column, with the gate reading the two terms above.
+///
+/// Where `FibChip` spends three columns and a copy pair per term, this spends one column and
+/// one row per term with no copies between rows, but the gate now spans three rotations, so
+/// every term is opened at three points instead of one.
+pub struct SingleColumnChip<F: FieldExt> {
+    config: SingleColumnConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SingleColumnChip<F> {
+    pub fn construct(config: SingleColumnConfig) -> SingleColumnChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_single_column(
+        column: Column<Advice>,
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> SingleColumnConfig {
+        let selector = cs.selector();
+
+        cs.enable_equality(column);
+        cs.enable_equality(instance);
+
+        cs.create_gate("single column add", |cells| {
+            let s = cells.query_selector(selector);
+            let a = cells.query_advice(column, Rotation(-2));
+            let b = cells.query_advice(column, Rotation::prev());
+            let c = cells.query_advice(column, Rotation::cur());
+
+            vec![s * (a + b - c)]
+        });
+
+        SingleColumnConfig {
+            column,
+            selector,
+            instance,
+        }
+    }
+
+    /// Reads the seeds from instance rows 0 and 1 into the first two rows and returns the cell
+    /// holding F_{fib_size}
+    pub fn assign_single_column(
+        &self,
+        mut layouter: impl Layouter<F>,
+        fib_size: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = &self.config;
+        if fib_size < 3 {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "single column",
+            |mut region| {
+                let mut a = region.assign_advice_from_instance(
+                    || "a",
+                    config.instance,
+                    0,
+                    config.column,
+                    0,
+                )?;
+                let mut b = region.assign_advice_from_instance(
+                    || "b",
+                    config.instance,
+                    1,
+                    config.column,
+                    1,
+                )?;
+
+                for row in 2..fib_size {
+                    config.selector.enable(&mut region, row)?;
+
+                    let c_value = a.value().copied() + b.value();
+                    let c = region.assign_advice(|| "c", config.column, row, || c_value)?;
+                    a = b;
+                    b = c;
+                }
+
+                Ok(b)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, OUT_ROW)
+    }
+}
+
+/// `FibCircuit` with `expose_output`, laid out in a single advice column
+#[derive(Clone, Debug, Default)]
+pub struct SingleColumnCircuit {
+    pub fib_size: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for SingleColumnCircuit {
+    type Config = SingleColumnConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let column = cs.advice_column();
+        let instance = cs.instance_column();
+
+        SingleColumnChip::configure_single_column(column, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = SingleColumnChip::construct(config);
+
+        let out = chip.assign_single_column(layouter.namespace(|| "sequence"), self.fib_size)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::{compute, FibCircuit};
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn matches_multi_column() {
+        let (a, b) = (Fr::from(2), Fr::from(7));
+        let instance = vec![a, b, compute(20, a, b)];
+
+        let single = SingleColumnCircuit { fib_size: 20 };
+        let prover = MockProver::<Fr>::run(5, &single, vec![instance.clone()]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let multi = FibCircuit {
+            fib_size: 20,
+            expose_output: true,
+            ..Default::default()
+        };
+        let prover = MockProver::<Fr>::run(5, &multi, vec![instance.clone()]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let mut wrong = instance;
+        wrong[2] += Fr::from(1);
+        let prover = MockProver::<Fr>::run(5, &single, vec![wrong]).unwrap();
+        assert!(prover.verify().is_err());
+
+        let mut cs = ConstraintSystem::<Fr>::default();
+        <SingleColumnCircuit as Circuit<Fr>>::configure(&mut cs);
+        assert_eq!(cs.num_advice_columns(), 1);
+    }
+}
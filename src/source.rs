@@ -0,0 +1,66 @@
+/// Supplies the seeds and length at synthesis time, so they can come from a database or
+/// RPC instead of living in the circuit struct. Plug one into `FibCircuit::from_source`.
+pub trait WitnessSource<F> {
+    fn seeds(&self) -> (F, F);
+    fn size(&self) -> usize;
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct StaticSource<F> {
+    pub seeds: (F, F),
+    pub size: usize,
+}
+
+impl<F: Copy> WitnessSource<F> for StaticSource<F> {
+    fn seeds(&self) -> (F, F) {
+        self.seeds
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::{compute, FibCircuit};
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
+
+    // Stands in for a row fetched by id from some store
+    #[derive(Clone, Default)]
+    struct AccountSource {
+        id: u64,
+    }
+
+    impl WitnessSource<Fr> for AccountSource {
+        fn seeds(&self) -> (Fr, Fr) {
+            (Fr::from(self.id), Fr::from(self.id * 2))
+        }
+
+        fn size(&self) -> usize {
+            10
+        }
+    }
+
+    #[test]
+    fn custom_source() {
+        let circuit = FibCircuit::from_source(AccountSource { id: 7 });
+        let out = compute(10, Fr::from(7), Fr::from(14));
+
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![vec![out]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let circuit = FibCircuit::from_source(StaticSource {
+            seeds: (Fr::from(1), Fr::from(1)),
+            size: 10,
+        });
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![vec![Fr::from(55)]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        // The seeds are private, so a wrong output has nothing to hide behind
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![vec![Fr::from(56)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::circuit::FibCircuit;
+use crate::error::Error;
+use crate::InstanceLayout;
+use serde::{Deserialize, Serialize};
+
+/// Bumped whenever a field is added or its meaning changes
+pub const SPEC_VERSION: u32 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SequenceKind {
+    Fibonacci,
+}
+
+/// Everything a prover and verifier must agree on before a proof is exchanged
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CircuitSpec {
+    pub version: u32,
+    pub sequence: SequenceKind,
+    pub fib_size: usize,
+    /// Weights of the recurrence `c = w0 * a + w1 * b`
+    pub coefficients: [u64; 2],
+    /// Number of rows the verifier supplies in the instance column
+    pub instance_len: usize,
+    /// Instance rows of the seeds and output. Defaulted so a version 1 spec still parses and
+    /// is turned away by the version check rather than as malformed JSON.
+    #[serde(default)]
+    pub instance_layout: InstanceLayout,
+}
+
+impl CircuitSpec {
+    pub fn fibonacci(fib_size: usize) -> Self {
+        Self {
+            version: SPEC_VERSION,
+            sequence: SequenceKind::Fibonacci,
+            fib_size,
+            coefficients: [1, 1],
+            instance_len: 2,
+            instance_layout: InstanceLayout::default(),
+        }
+    }
+}
+
+impl FibCircuit {
+    pub fn from_spec(spec: &CircuitSpec) -> Result<Self, Error> {
+        if spec.version != SPEC_VERSION {
+            return Err(Error::SpecVersion {
+                expected: SPEC_VERSION,
+                found: spec.version,
+            });
+        }
+        // The gate is hardwired to a + b - c with both seeds read from the instance
+        if spec.coefficients != [1, 1] {
+            return Err(Error::UnsupportedSpec(format!(
+                "coefficients {:?}",
+                spec.coefficients
+            )));
+        }
+        // Seeds, optionally followed by the exposed output
+        let expose_output = match spec.instance_len {
+            2 => false,
+            3 => true,
+            len => return Err(Error::UnsupportedSpec(format!("instance length {len}"))),
+        };
+
+        let circuit = Self {
+            fib_size: spec.fib_size,
+            expose_output,
+            instance_layout: spec.instance_layout.clone(),
+            ..Default::default()
+        };
+        // A layout sending a seed or the output past the end of the instance can never be met
+        if circuit.check_instance_len(spec.instance_len).is_err() {
+            return Err(Error::UnsupportedSpec(format!(
+                "instance layout {:?} for {} rows",
+                spec.instance_layout, spec.instance_len
+            )));
+        }
+
+        Ok(circuit)
+    }
+
+    pub fn spec(&self) -> CircuitSpec {
+        CircuitSpec {
+            instance_len: if self.expose_output { 3 } else { 2 },
+            instance_layout: self.instance_layout.clone(),
+            ..CircuitSpec::fibonacci(self.fib_size)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prover::{gen_params, gen_pk, min_k_for, prove_fib, verify_fib};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn spec_round_trip() {
+        let json = serde_json::to_string(&CircuitSpec::fibonacci(10)).unwrap();
+        let spec: CircuitSpec = serde_json::from_str(&json).unwrap();
+        let circuit = FibCircuit::from_spec(&spec).unwrap();
+
+        let params = gen_params(min_k_for(spec.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+        verify_fib(&params, pk.get_vk(), &instances, &proof).unwrap();
+
+        let stale = CircuitSpec {
+            version: SPEC_VERSION + 1,
+            ..spec
+        };
+        assert!(matches!(
+            FibCircuit::from_spec(&stale),
+            Err(Error::SpecVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn layout_round_trip() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            instance_layout: InstanceLayout::OutputFirst,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&circuit.spec()).unwrap();
+        let spec: CircuitSpec = serde_json::from_str(&json).unwrap();
+        assert_eq!(spec.instance_layout, InstanceLayout::OutputFirst);
+        assert_eq!(FibCircuit::from_spec(&spec).unwrap().spec(), circuit.spec());
+
+        // Output first, but with no output row to put it in
+        let short = CircuitSpec {
+            instance_len: 2,
+            ..spec.clone()
+        };
+        assert!(matches!(
+            FibCircuit::from_spec(&short),
+            Err(Error::UnsupportedSpec(_))
+        ));
+
+        // A version 1 spec has no layout and is rejected for its version
+        let mut v1 = serde_json::to_value(CircuitSpec::fibonacci(10)).unwrap();
+        v1["version"] = 1.into();
+        v1.as_object_mut().unwrap().remove("instance_layout");
+        let v1: CircuitSpec = serde_json::from_value(v1).unwrap();
+        assert!(matches!(
+            FibCircuit::from_spec(&v1),
+            Err(Error::SpecVersion { .. })
+        ));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::error::Error;
+use crate::prover::verify_fib;
+use halo2_base::halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::VerifyingKey,
+    poly::kzg::commitment::ParamsKZG,
+};
+
+/// Collects a proof that arrives in chunks and verifies it once the sender is done.
+///
+/// `verify_proof` reads the transcript in one pass and needs every commitment before the
+/// first challenge, so the bytes are buffered rather than checked as they land. What this
+/// saves the caller is assembling and sizing the proof up front, e.g. when reading it off a
+/// socket.
+pub struct StreamingVerifier<'a> {
+    params: &'a ParamsKZG<Bn256>,
+    buffer: Vec<u8>,
+}
+
+impl<'a> StreamingVerifier<'a> {
+    pub fn new(params: &'a ParamsKZG<Bn256>) -> Self {
+        Self {
+            params,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Bytes received so far
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    pub fn finalize(self, vk: &VerifyingKey<G1Affine>, instances: &[Vec<Fr>]) -> Result<(), Error> {
+        verify_fib(self.params, vk, instances, &self.buffer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::FibCircuit;
+    use crate::prover::{gen_params, gen_pk, min_k_for, prove_fib};
+
+    #[test]
+    fn proof_in_chunks() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+
+        let mut verifier = StreamingVerifier::new(&params);
+        for chunk in proof.chunks(16) {
+            verifier.feed(chunk);
+        }
+        assert_eq!(verifier.len(), proof.len());
+        verifier.finalize(pk.get_vk(), &instances).unwrap();
+
+        // A stream cut short fails at the end rather than verifying a prefix
+        let mut verifier = StreamingVerifier::new(&params);
+        for chunk in proof.chunks(16).take(proof.len() / 16 - 1) {
+            verifier.feed(chunk);
+        }
+        assert!(verifier.finalize(pk.get_vk(), &instances).is_err());
+    }
+}
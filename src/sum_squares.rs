@@ -0,0 +1,218 @@
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [seed_a, seed_b, sum of squares]
+const SUM_ROW: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct SumSquaresConfig {
+    pub fib: FibConfig,
+    /// F_1..=F_{n+1}, copied out of the chain one per row
+    pub term: Column<Advice>,
+    /// F_1^2 + ... + F_i^2 on row i - 1
+    pub acc: Column<Advice>,
+    /// F_n F_{n+1}, on the last summed row only
+    pub product: Column<Advice>,
+    pub first: Selector,
+    pub step: Selector,
+    pub identity: Selector,
+}
+
+/// Checks sum_{i=1}^{n} F_i^2 = F_n F_{n+1} against a `FibChip` chain.
+///
+/// The chain's terms are copied into one column, `acc` squares and sums them down the region,
+/// and on row n - 1 the product of that term and the next is constrained equal to the total.
+/// Like the other identities, it only holds for the seeds (1, 1).
+pub struct SumSquaresChip<F: FieldExt> {
+    config: SumSquaresConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SumSquaresChip<F> {
+    pub fn construct(config: SumSquaresConfig) -> SumSquaresChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_sum_squares(
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> SumSquaresConfig {
+        let fib = FibChip::configure(advice, instance, cs);
+        let term = cs.advice_column();
+        let acc = cs.advice_column();
+        let product = cs.advice_column();
+        let first = cs.selector();
+        let step = cs.selector();
+        let identity = cs.selector();
+
+        cs.enable_equality(term);
+        cs.enable_equality(acc);
+
+        cs.create_gate("squares first", |cells| {
+            let s = cells.query_selector(first);
+            let term = cells.query_advice(term, Rotation::cur());
+            let acc = cells.query_advice(acc, Rotation::cur());
+
+            vec![s * (term.clone() * term - acc)]
+        });
+
+        cs.create_gate("squares step", |cells| {
+            let s = cells.query_selector(step);
+            let term = cells.query_advice(term, Rotation::cur());
+            let acc = cells.query_advice(acc, Rotation::cur());
+            let acc_prev = cells.query_advice(acc, Rotation::prev());
+
+            vec![s * (acc_prev + term.clone() * term - acc)]
+        });
+
+        cs.create_gate("squares identity", |cells| {
+            let s = cells.query_selector(identity);
+            let term = cells.query_advice(term, Rotation::cur());
+            let term_next = cells.query_advice(term, Rotation::next());
+            let acc = cells.query_advice(acc, Rotation::cur());
+            let product = cells.query_advice(product, Rotation::cur());
+
+            vec![
+                s.clone() * (term * term_next - product.clone()),
+                s * (acc - product),
+            ]
+        });
+
+        SumSquaresConfig {
+            fib,
+            term,
+            acc,
+            product,
+            first,
+            step,
+            identity,
+        }
+    }
+
+    /// Sums the squares of all but the last of `terms` (F_1..=F_{n+1}) and returns the cell
+    /// holding the total
+    pub fn assign_sum_squares(
+        &self,
+        mut layouter: impl Layouter<F>,
+        terms: &[AssignedCell<F, F>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = &self.config;
+        if terms.len() < 2 {
+            return Err(Error::Synthesis);
+        }
+        let n = terms.len() - 1;
+
+        layouter.assign_region(
+            || "sum of squares",
+            |mut region| {
+                let mut acc = Value::known(F::zero());
+                let mut out = None;
+
+                for (row, term) in terms.iter().enumerate() {
+                    let term = term.copy_advice(|| "term", &mut region, config.term, row)?;
+                    if row == n {
+                        break;
+                    }
+
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else {
+                        config.step.enable(&mut region, row)?;
+                    }
+                    acc = acc + term.value().map(|t| t.square());
+                    let acc_cell = region.assign_advice(|| "acc", config.acc, row, || acc)?;
+
+                    if row == n - 1 {
+                        config.identity.enable(&mut region, row)?;
+                        let product = term.value().copied() * terms[n].value();
+                        region.assign_advice(|| "product", config.product, row, || product)?;
+                        out = Some(acc_cell);
+                    }
+                }
+
+                out.ok_or(Error::Synthesis)
+            },
+        )
+    }
+}
+
+/// Proves the sum of the squares of F_1..=F_n at instance row 2 matches F_n F_{n+1}
+#[derive(Clone, Debug, Default)]
+pub struct SumSquaresCircuit {
+    pub n: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for SumSquaresCircuit {
+    type Config = SumSquaresConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let instance = cs.instance_column();
+
+        SumSquaresChip::configure_sum_squares(advice, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // The chain runs to F_{n+1}, and the first row already yields F_3
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let fib = FibChip::construct(config.fib.clone());
+        let (a, mut b, mut c) = fib.assign_first_row(layouter.namespace(|| "first row"))?;
+        let mut terms = vec![a, b.clone(), c.clone()];
+        for _ in 3..self.n + 1 {
+            let new_c = fib.assign_row(layouter.namespace(|| "next_row"), &b, &c)?;
+            terms.push(new_c.clone());
+            b = c;
+            c = new_c;
+        }
+
+        let chip = SumSquaresChip::construct(config);
+        let sum = chip.assign_sum_squares(layouter.namespace(|| "squares"), &terms)?;
+        fib.expose_public(layouter.namespace(|| "sum of squares"), &sum, SUM_ROW)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn squares_up_to_f_10() {
+        // 1 + 1 + 4 + 9 + 25 + 64 + 169 + 441 + 1156 + 3025 = 4895 = 55 * 89
+        let circuit = SumSquaresCircuit { n: 10 };
+        let one = Fr::from(1);
+
+        let prover =
+            MockProver::<Fr>::run(5, &circuit, vec![vec![one, one, Fr::from(4895)]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        let prover =
+            MockProver::<Fr>::run(5, &circuit, vec![vec![one, one, Fr::from(4894)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // Other seeds break the identity itself
+        let prover =
+            MockProver::<Fr>::run(5, &circuit, vec![vec![one, Fr::from(2), Fr::from(4895)]])
+                .unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
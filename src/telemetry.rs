@@ -0,0 +1,68 @@
+//! Arithmetic-level counters for comparing layouts independently of the prover.
+//!
+//! Counts are recorded once per chip call rather than inside region closures, since the
+//! floor planner may run those closures more than once. Without the `telemetry` feature
+//! `record` is an empty inline function.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpCounts {
+    pub additions: usize,
+    pub copies: usize,
+}
+
+#[cfg(feature = "telemetry")]
+thread_local! {
+    static COUNTS: std::cell::Cell<OpCounts> = std::cell::Cell::new(OpCounts::default());
+}
+
+#[inline(always)]
+pub(crate) fn record(additions: usize, copies: usize) {
+    #[cfg(feature = "telemetry")]
+    COUNTS.with(|counts| {
+        let mut current = counts.get();
+        current.additions += additions;
+        current.copies += copies;
+        counts.set(current);
+    });
+    #[cfg(not(feature = "telemetry"))]
+    let _ = (additions, copies);
+}
+
+/// Runs `f` (typically a `MockProver::run`) and returns the operations it performed on this
+/// thread
+#[cfg(feature = "telemetry")]
+pub fn count_ops<R>(f: impl FnOnce() -> R) -> (R, OpCounts) {
+    COUNTS.with(|counts| counts.set(OpCounts::default()));
+    let result = f();
+    (result, COUNTS.with(|counts| counts.take()))
+}
+
+#[cfg(all(test, feature = "telemetry"))]
+mod test {
+    use super::*;
+    use crate::circuit::FibCircuit;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn unpacked_chip_counts() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let instance = vec![vec![Fr::from(1), Fr::from(1)]];
+
+        let (prover, counts) = count_ops(|| MockProver::run(4, &circuit, instance).unwrap());
+        assert_satisfied_verbose(&prover);
+
+        // F_1 and F_2 are seeds, every later term is one addition; each row copies two cells
+        assert_eq!(
+            counts,
+            OpCounts {
+                additions: 8,
+                copies: 16,
+            }
+        );
+    }
+}
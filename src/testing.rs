@@ -0,0 +1,272 @@
+use crate::circuit::{compute, FibCircuit};
+use crate::digest::TraceDigestConfig;
+use crate::error::Error;
+use crate::prover::{gen_pk, insecure_seeded_params, min_k_for, prove_fib, verify_fib};
+use crate::FibChip;
+use halo2_base::halo2_proofs::{
+    circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    halo2curves::{
+        bn256::{Bn256, Fr, G1Affine},
+        FieldExt,
+    },
+    plonk::{self, Circuit, ConstraintSystem, VerifyingKey},
+    poly::kzg::commitment::ParamsKZG,
+};
+use std::marker::PhantomData;
+
+/// Backend for `prove_and_check`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// `MockProver`: fast, and reports which constraints failed
+    Mock,
+    /// Seeded KZG setup, keygen, a SHPLONK proof and its verification
+    Real,
+}
+
+/// Proves `circuit` against `instances` at `k` with `mode` and checks the result, so one test
+/// body can cover both backends. A mock failure comes back as `Error::MockFailure`, a proof
+/// that does not verify as `Error::Halo2`.
+pub fn prove_and_check<C: Circuit<Fr>>(
+    k: u32,
+    circuit: C,
+    instances: Vec<Vec<Fr>>,
+    mode: Mode,
+) -> Result<(), Error> {
+    match mode {
+        Mode::Mock => MockProver::run(k, &circuit, instances)?
+            .verify()
+            .map_err(Error::MockFailure),
+        Mode::Real => {
+            let params = insecure_seeded_params(k);
+            let pk = gen_pk(&params, &circuit)?;
+            let proof = prove_fib(&params, &pk, circuit, &instances, false)?;
+            verify_fib(&params, pk.get_vk(), &instances, &proof)
+        }
+    }
+}
+
+/// How `StubbedTraceCircuit` turns the terms it witnesses into its one public value
+pub trait TraceCommitment {
+    /// Instance row the commitment is exposed at
+    const ROW: usize;
+
+    fn commit(
+        config: &TraceDigestConfig<Fr>,
+        layouter: impl Layouter<Fr>,
+        terms: &[AssignedCell<Fr, Fr>],
+    ) -> Result<AssignedCell<Fr, Fr>, plonk::Error>;
+}
+
+/// Witnesses the given terms directly, with no add gate, and exposes only their commitment
+/// under `C`, so a test can hand a commitment circuit a trace no honest prover would produce
+pub struct StubbedTraceCircuit<C> {
+    pub terms: Vec<Fr>,
+    _commitment: PhantomData<C>,
+}
+
+impl<C> StubbedTraceCircuit<C> {
+    pub fn new(terms: Vec<Fr>) -> Self {
+        Self {
+            terms,
+            _commitment: PhantomData,
+        }
+    }
+}
+
+impl<C: TraceCommitment> Circuit<Fr> for StubbedTraceCircuit<C> {
+    type Config = TraceDigestConfig<Fr>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.terms.clone())
+    }
+
+    fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+        TraceDigestConfig::configure(cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), plonk::Error> {
+        let terms = layouter.assign_region(
+            || "stubbed terms",
+            |mut region| {
+                let column = config.fib.advice[0];
+                let mut cells = vec![];
+                for (row, term) in self.terms.iter().enumerate() {
+                    let term = Value::known(*term);
+                    cells.push(region.assign_advice(|| "term", column, row, || term)?);
+                }
+                Ok(cells)
+            },
+        )?;
+
+        let commitment = C::commit(&config, layouter.namespace(|| "commitment"), &terms)?;
+        let chip = FibChip::construct(config.fib);
+        chip.expose_public(layouter.namespace(|| "commitment"), &commitment, C::ROW)
+    }
+}
+
+/// `MockProver::assert_satisfied` with the failures laid out as a table, one row per failure
+/// giving its kind and halo2's description of the constraint, location and cell values
+pub fn assert_satisfied_verbose<F: FieldExt>(prover: &MockProver<F>) {
+    if let Err(failures) = prover.verify() {
+        let report = failure_table(&failures);
+        eprintln!("{report}");
+        panic!("circuit is not satisfied:\n{report}");
+    }
+}
+
+fn failure_table(failures: &[VerifyFailure]) -> String {
+    let mut table = format!("{:>3}  {:<24}  details\n", "#", "kind");
+    for (i, failure) in failures.iter().enumerate() {
+        let kind = match failure {
+            VerifyFailure::CellNotAssigned { .. } => "cell not assigned",
+            VerifyFailure::ConstraintNotSatisfied { .. } => "constraint not satisfied",
+            VerifyFailure::ConstraintPoisoned { .. } => "constraint poisoned",
+            VerifyFailure::Lookup { .. } => "lookup",
+            VerifyFailure::Permutation { .. } => "permutation",
+            #[allow(unreachable_patterns)]
+            _ => "other",
+        };
+        // Continuation lines of multi-line descriptions stay under the details column
+        let details = failure.to_string().replace('\n', &format!("\n{:31}", ""));
+        table.push_str(&format!("{i:>3}  {kind:<24}  {details}\n"));
+    }
+    table
+}
+
+/// Runs `FibCircuit` with the output exposed through `MockProver` for every
+/// `(fib_size, seed_a, seed_b)` and panics once at the end, listing each case that failed
+/// along with its failures.
+pub fn run_mock_cases(cases: &[(usize, u64, u64)]) {
+    let mut report = String::new();
+
+    for &(fib_size, a, b) in cases {
+        let circuit = FibCircuit {
+            fib_size,
+            expose_output: true,
+            ..Default::default()
+        };
+        let (a, b) = (Fr::from(a), Fr::from(b));
+        let instances = vec![vec![a, b, compute(fib_size, a, b)]];
+
+        let result = MockProver::run(min_k_for(fib_size), &circuit, instances)
+            .map_err(|err| format!("{err}\n"))
+            .and_then(|prover| prover.verify().map_err(|failures| failure_table(&failures)));
+
+        if let Err(failures) = result {
+            report.push_str(&format!(
+                "fib_size = {fib_size}, seeds = ({a:?}, {b:?}):\n{failures}"
+            ));
+        }
+    }
+
+    assert!(report.is_empty(), "mock cases failed:\n{report}");
+}
+
+/// Flips one bit of `proof` at each byte offset in `offsets` (bit `offset % 8`, so a sweep
+/// touches every bit position) and verifies each mutant separately. Returns how many were
+/// accepted; any count above zero is a soundness bug.
+pub fn mutate_and_verify(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    proof: &[u8],
+    offsets: &[usize],
+) -> usize {
+    offsets
+        .iter()
+        .filter(|&&offset| {
+            let mut mutant = proof.to_vec();
+            mutant[offset] ^= 1 << (offset % 8);
+            verify_fib(params, vk, instances, &mutant).is_ok()
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn both_backends_agree() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let k = min_k_for(circuit.fib_size);
+        let one = Fr::from(1);
+
+        for mode in [Mode::Mock, Mode::Real] {
+            let good = vec![vec![one, one, Fr::from(55)]];
+            prove_and_check(k, circuit.clone(), good, mode).unwrap();
+
+            let bad = vec![vec![one, one, Fr::from(56)]];
+            assert!(
+                prove_and_check(k, circuit.clone(), bad, mode).is_err(),
+                "{mode:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn case_matrix() {
+        let mut cases = vec![];
+        for fib_size in [3, 4, 10, 100] {
+            for (a, b) in [(0, 1), (1, 1), (2, 7), (u64::MAX, 3)] {
+                cases.push((fib_size, a, b));
+            }
+        }
+        run_mock_cases(&cases);
+    }
+
+    #[test]
+    #[should_panic(expected = "fib_size = 2")]
+    fn failures_are_reported() {
+        run_mock_cases(&[(10, 1, 1), (2, 1, 1)]);
+    }
+
+    #[test]
+    fn mutated_proofs_are_rejected() {
+        use crate::prover::{gen_params, gen_pk, prove_fib};
+
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instances = vec![vec![Fr::from(1), Fr::from(1), Fr::from(55)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+
+        let offsets: Vec<_> = (0..proof.len()).step_by(5).collect();
+        assert_eq!(
+            mutate_and_verify(&params, pk.get_vk(), &instances, &proof, &offsets),
+            0
+        );
+        assert_eq!(
+            mutate_and_verify(&params, pk.get_vk(), &instances, &proof, &[]),
+            0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "permutation")]
+    fn verbose_report_names_failure() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let instances = vec![vec![Fr::from(1), Fr::from(1), Fr::from(56)]];
+        let prover = MockProver::run(5, &circuit, instances).unwrap();
+
+        assert_satisfied_verbose(&prover);
+    }
+}
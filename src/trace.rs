@@ -0,0 +1,211 @@
+//! Verification with every transcript operation logged, for tracking down where a prover and
+//! a verifier (or two verifier implementations) stop agreeing on the transcript.
+
+use crate::error::Error;
+use crate::prover::{MultiOpen, ProofHeader, TranscriptHash};
+use halo2_base::halo2_proofs::{
+    halo2curves::{
+        bn256::{Bn256, Fr, G1Affine},
+        group::{ff::PrimeField, GroupEncoding},
+    },
+    plonk::{verify_proof, VerifyingKey},
+    poly::{
+        commitment::{ParamsProver, Verifier},
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{VerifierGWC, VerifierSHPLONK},
+            strategy::SingleStrategy,
+        },
+        VerificationStrategy,
+    },
+    transcript::{
+        Blake2bRead, Challenge255, Keccak256Read, Transcript, TranscriptRead, TranscriptReadBuffer,
+    },
+};
+use std::io;
+
+/// One transcript operation during verification. Offsets are into the whole proof, header
+/// included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranscriptEvent {
+    /// A commitment read from the proof
+    ReadPoint { offset: usize, len: usize },
+    /// An evaluation read from the proof
+    ReadScalar { offset: usize, len: usize },
+    /// A point absorbed without being read, e.g. an instance commitment
+    CommonPoint,
+    /// A scalar absorbed without being read, e.g. the verifying key's digest
+    CommonScalar,
+    /// A challenge drawn once the proof had been read up to `offset`
+    Squeeze { offset: usize },
+}
+
+// A transcript reader with each call recorded on the way through
+struct TracingTranscript<T> {
+    inner: T,
+    offset: usize,
+    events: Vec<TranscriptEvent>,
+}
+
+impl<T: TranscriptRead<G1Affine, Challenge255<G1Affine>>>
+    Transcript<G1Affine, Challenge255<G1Affine>> for TracingTranscript<T>
+{
+    fn squeeze_challenge(&mut self) -> Challenge255<G1Affine> {
+        self.events.push(TranscriptEvent::Squeeze {
+            offset: self.offset,
+        });
+        self.inner.squeeze_challenge()
+    }
+
+    fn common_point(&mut self, point: G1Affine) -> io::Result<()> {
+        self.events.push(TranscriptEvent::CommonPoint);
+        self.inner.common_point(point)
+    }
+
+    fn common_scalar(&mut self, scalar: Fr) -> io::Result<()> {
+        self.events.push(TranscriptEvent::CommonScalar);
+        self.inner.common_scalar(scalar)
+    }
+}
+
+impl<T: TranscriptRead<G1Affine, Challenge255<G1Affine>>>
+    TranscriptRead<G1Affine, Challenge255<G1Affine>> for TracingTranscript<T>
+{
+    fn read_point(&mut self) -> io::Result<G1Affine> {
+        let point = self.inner.read_point()?;
+        let len = <G1Affine as GroupEncoding>::Repr::default().as_ref().len();
+        self.events.push(TranscriptEvent::ReadPoint {
+            offset: self.offset,
+            len,
+        });
+        self.offset += len;
+        Ok(point)
+    }
+
+    fn read_scalar(&mut self) -> io::Result<Fr> {
+        let scalar = self.inner.read_scalar()?;
+        let len = <Fr as PrimeField>::Repr::default().as_ref().len();
+        self.events.push(TranscriptEvent::ReadScalar {
+            offset: self.offset,
+            len,
+        });
+        self.offset += len;
+        Ok(scalar)
+    }
+}
+
+/// `verify_fib` recording every transcript operation, under whichever scheme and hash the
+/// header names. The events are returned whether or not the proof verifies, up to the point
+/// verification stopped; a proof whose header does not parse has none.
+pub fn trace_verify(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    proof: &[u8],
+) -> (Vec<TranscriptEvent>, Result<(), Error>) {
+    let (header, body) = match ProofHeader::parse(proof) {
+        Ok(parsed) => parsed,
+        Err(err) => return (vec![], Err(err)),
+    };
+
+    match (header.scheme, header.transcript) {
+        (MultiOpen::Shplonk, TranscriptHash::Blake2b) => {
+            traced::<VerifierSHPLONK<'_, Bn256>, _>(params, vk, instances, Blake2bRead::init(body))
+        }
+        (MultiOpen::Shplonk, TranscriptHash::Keccak256) => traced::<VerifierSHPLONK<'_, Bn256>, _>(
+            params,
+            vk,
+            instances,
+            Keccak256Read::init(body),
+        ),
+        (MultiOpen::Gwc, TranscriptHash::Blake2b) => {
+            traced::<VerifierGWC<'_, Bn256>, _>(params, vk, instances, Blake2bRead::init(body))
+        }
+        (MultiOpen::Gwc, TranscriptHash::Keccak256) => {
+            traced::<VerifierGWC<'_, Bn256>, _>(params, vk, instances, Keccak256Read::init(body))
+        }
+    }
+}
+
+fn traced<'params, V, T>(
+    params: &'params ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[Vec<Fr>],
+    inner: T,
+) -> (Vec<TranscriptEvent>, Result<(), Error>)
+where
+    V: Verifier<'params, KZGCommitmentScheme<Bn256>>,
+    SingleStrategy<'params, Bn256>: VerificationStrategy<'params, KZGCommitmentScheme<Bn256>, V>,
+    T: TranscriptRead<G1Affine, Challenge255<G1Affine>>,
+{
+    let instances: Vec<&[Fr]> = instances.iter().map(Vec::as_slice).collect();
+    let mut transcript = TracingTranscript {
+        inner,
+        offset: ProofHeader::LEN,
+        events: vec![],
+    };
+
+    let result = verify_proof::<
+        KZGCommitmentScheme<Bn256>,
+        V,
+        Challenge255<G1Affine>,
+        TracingTranscript<T>,
+        SingleStrategy<'params, Bn256>,
+    >(
+        params.verifier_params(),
+        vk,
+        SingleStrategy::new(params),
+        &[&instances[..]],
+        &mut transcript,
+    );
+    (transcript.events, result.map(|_| ()).map_err(Error::from))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::FibCircuit;
+    use crate::prover::{gen_params, gen_pk, min_k_for, prove_fib};
+
+    #[test]
+    fn events_cover_proof() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+        let instances = vec![vec![Fr::from(1), Fr::from(1)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+
+        let (events, result) = trace_verify(&params, pk.get_vk(), &instances, &proof);
+        result.unwrap();
+
+        // The verifying key's digest goes in before anything is read
+        assert_eq!(events[0], TranscriptEvent::CommonScalar);
+
+        // Reads tile the proof from the end of the header to its last byte
+        let mut expected = ProofHeader::LEN;
+        for event in &events {
+            match *event {
+                TranscriptEvent::ReadPoint { offset, len }
+                | TranscriptEvent::ReadScalar { offset, len } => {
+                    assert_eq!(offset, expected);
+                    expected += len;
+                }
+                TranscriptEvent::Squeeze { offset } => assert_eq!(offset, expected),
+                _ => {}
+            }
+        }
+        assert_eq!(expected, proof.len());
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, TranscriptEvent::Squeeze { .. })));
+
+        // Same reads for a proof of the wrong statement; only the outcome differs
+        let wrong = vec![vec![Fr::from(1), Fr::from(2)]];
+        let (wrong_events, result) = trace_verify(&params, pk.get_vk(), &wrong, &proof);
+        assert!(result.is_err());
+        assert_eq!(wrong_events, events);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::circuit::FibCircuit;
+use crate::encoding::{decode_instance, Endianness};
+use crate::error::Error;
+use crate::prover::{params_for_k, verify_fib};
+use halo2_base::halo2_proofs::{
+    halo2curves::bn256::{Bn256, G1Affine},
+    plonk::VerifyingKey,
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+    SerdeFormat,
+};
+
+/// Verifies a `FibCircuit` proof from bytes alone: the instance as `encode_instance` writes it
+/// (little-endian), the verifying key in `SerdeFormat::RawBytes` and the params the proof was
+/// made under as `Params::write` writes them.
+///
+/// Params larger than the key's `k` are cut down to it, so one published setup serves every
+/// key. Anything malformed is reported as `false`.
+pub fn verify(proof: &[u8], instance: &[u8], vk_bytes: &[u8], params_bytes: &[u8]) -> bool {
+    try_verify(proof, instance, vk_bytes, params_bytes).is_ok()
+}
+
+fn try_verify(
+    proof: &[u8],
+    instance: &[u8],
+    vk_bytes: &[u8],
+    mut params_bytes: &[u8],
+) -> Result<(), Error> {
+    let vk = VerifyingKey::<G1Affine>::from_bytes::<FibCircuit>(vk_bytes, SerdeFormat::RawBytes)?;
+    let instances = decode_instance(instance, Endianness::Little)?;
+    let params = ParamsKZG::<Bn256>::read(&mut params_bytes)?;
+    let params = params_for_k(&params, vk.get_domain().k())?;
+
+    verify_fib(&params, &vk, &instances, proof)
+}
+
+/// `verify` exported to JavaScript. Build it without the default `prover` feature so the
+/// proving code and its dependencies stay out of the bundle.
+#[cfg(feature = "wasm-verify")]
+#[wasm_bindgen::prelude::wasm_bindgen(js_name = verify)]
+pub fn verify_wasm(proof: &[u8], instance: &[u8], vk_bytes: &[u8], params_bytes: &[u8]) -> bool {
+    verify(proof, instance, vk_bytes, params_bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encoding::encode_instance;
+    use crate::prover::{gen_params, gen_pk, min_k_for, prove_fib};
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn verify_from_bytes() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let instances = vec![vec![Fr::from(1), Fr::from(1), Fr::from(55)]];
+        let proof = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+        let vk_bytes = pk.get_vk().to_bytes(SerdeFormat::RawBytes);
+
+        let mut params_bytes = vec![];
+        params.write(&mut params_bytes).unwrap();
+
+        let instance = encode_instance(&instances, Endianness::Little);
+        assert!(verify(&proof, &instance, &vk_bytes, &params_bytes));
+
+        let wrong = encode_instance(
+            &[vec![Fr::from(1), Fr::from(1), Fr::from(56)]],
+            Endianness::Little,
+        );
+        assert!(!verify(&proof, &wrong, &vk_bytes, &params_bytes));
+        assert!(!verify(&proof, &instance, &vk_bytes[1..], &params_bytes));
+
+        // Params from another setup cannot open the proof's commitments
+        let mut other = vec![];
+        gen_params(params.k()).write(&mut other).unwrap();
+        assert!(!verify(&proof, &instance, &vk_bytes, &other));
+    }
+}
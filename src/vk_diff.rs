@@ -0,0 +1,134 @@
+use halo2_base::halo2_proofs::{halo2curves::bn256::G1Affine, plonk::VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// The shape of a verifying key: what a circuit change can move without touching a proof
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VkDescriptor {
+    pub k: u32,
+    pub advice_columns: usize,
+    /// After keygen, so selectors show up here once compressed into fixed columns
+    pub fixed_columns: usize,
+    pub instance_columns: usize,
+    pub gates: usize,
+    pub lookups: usize,
+    pub permutation_columns: usize,
+    pub degree: usize,
+}
+
+impl VkDescriptor {
+    pub fn from_vk(vk: &VerifyingKey<G1Affine>) -> Self {
+        let cs = vk.cs();
+        Self {
+            k: vk.get_domain().k(),
+            advice_columns: cs.num_advice_columns(),
+            fixed_columns: cs.num_fixed_columns(),
+            instance_columns: cs.num_instance_columns(),
+            gates: cs.gates().len(),
+            lookups: cs.lookups().len(),
+            permutation_columns: cs.permutation().get_columns().len(),
+            degree: cs.degree(),
+        }
+    }
+
+    // Field name and value, in declaration order
+    fn fields(&self) -> [(&'static str, usize); 8] {
+        [
+            ("k", self.k as usize),
+            ("advice_columns", self.advice_columns),
+            ("fixed_columns", self.fixed_columns),
+            ("instance_columns", self.instance_columns),
+            ("gates", self.gates),
+            ("lookups", self.lookups),
+            ("permutation_columns", self.permutation_columns),
+            ("degree", self.degree),
+        ]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VkChange {
+    pub field: String,
+    pub before: usize,
+    pub after: usize,
+}
+
+/// Every descriptor field that differs between two verifying keys
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VkDiff {
+    pub changes: Vec<VkChange>,
+}
+
+impl VkDiff {
+    /// No shape change. The keys can still differ in their fixed or permutation commitments,
+    /// e.g. from the same gates laid out on other rows.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn get(&self, field: &str) -> Option<&VkChange> {
+        self.changes.iter().find(|change| change.field == field)
+    }
+}
+
+pub fn diff_vk(vk_a: &VerifyingKey<G1Affine>, vk_b: &VerifyingKey<G1Affine>) -> VkDiff {
+    let a = VkDescriptor::from_vk(vk_a);
+    let b = VkDescriptor::from_vk(vk_b);
+
+    let changes = a
+        .fields()
+        .into_iter()
+        .zip(b.fields())
+        .filter(|((_, before), (_, after))| before != after)
+        .map(|((field, before), (_, after))| VkChange {
+            field: field.to_string(),
+            before,
+            after,
+        })
+        .collect();
+    VkDiff { changes }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuit::{FibCircuit, SumCircuit};
+    use crate::prover::{gen_params, gen_pk, min_k_for};
+
+    #[test]
+    fn base_against_sum() {
+        let params = gen_params(min_k_for(10));
+        let base = gen_pk(
+            &params,
+            &FibCircuit {
+                fib_size: 10,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let sum = gen_pk(
+            &params,
+            &SumCircuit {
+                n: 10,
+                check_identity: false,
+            },
+        )
+        .unwrap();
+
+        assert!(diff_vk(base.get_vk(), base.get_vk()).is_empty());
+
+        // One more column, in the permutation too, and the three running-sum gates
+        let diff = diff_vk(base.get_vk(), sum.get_vk());
+        let change = |field| {
+            let change = diff.get(field).unwrap();
+            (change.before, change.after)
+        };
+        assert_eq!(change("advice_columns"), (3, 4));
+        assert_eq!(change("permutation_columns"), (4, 5));
+        assert_eq!(change("gates"), (1, 4));
+        assert!(diff.get("k").is_none());
+        assert!(diff.get("instance_columns").is_none());
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["changes"][0]["field"], "advice_columns");
+    }
+}
@@ -0,0 +1,222 @@
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+/// Off-circuit term `size` of the recurrence `t_k = sum_i weights[i] * t_{k-W+i}` started from
+/// `seeds`, oldest first
+pub fn compute_window<F: FieldExt, const W: usize>(
+    size: usize,
+    seeds: [F; W],
+    weights: [u64; W],
+) -> F {
+    let mut terms = seeds.to_vec();
+    while terms.len() < size {
+        let window = &terms[terms.len() - W..];
+        let next = window
+            .iter()
+            .zip(weights)
+            .fold(F::zero(), |acc, (t, w)| acc + *t * F::from(w));
+        terms.push(next);
+    }
+    terms[size - 1]
+}
+
+#[derive(Debug, Clone)]
+pub struct WindowConfig<const W: usize> {
+    /// One term per row
+    pub term: Column<Advice>,
+    /// `weights[i]` multiplies the term `W - i` rows up
+    pub weights: [Column<Fixed>; W],
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// A linear recurrence over a window of the last `W` terms, `t = sum_i w_i * t_{-W+i}`.
+///
+/// Fibonacci is `W = 2` with weights (1, 1), tribonacci `W = 3` with (1, 1, 1), Pell `W = 2`
+/// with (1, 2). The terms run down a single column, so the gate reads the window through
+/// rotations, and each weight sits in its own fixed column on every enabled row, which puts
+/// the recurrence in the verifying key rather than in the witness.
+pub struct WindowChip<F: FieldExt, const W: usize> {
+    config: WindowConfig<W>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const W: usize> WindowChip<F, W> {
+    pub fn construct(config: WindowConfig<W>) -> WindowChip<F, W> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_window(
+        term: Column<Advice>,
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> WindowConfig<W> {
+        let weights = std::array::from_fn(|_| cs.fixed_column());
+        let selector = cs.selector();
+
+        cs.enable_equality(term);
+        cs.enable_equality(instance);
+
+        cs.create_gate("window", |cells| {
+            let s = cells.query_selector(selector);
+            let cur = cells.query_advice(term, Rotation::cur());
+            let sum = weights
+                .iter()
+                .enumerate()
+                .map(|(i, weight)| {
+                    let prev = cells.query_advice(term, Rotation(i as i32 - W as i32));
+                    cells.query_fixed(*weight, Rotation::cur()) * prev
+                })
+                .fold(Expression::Constant(F::zero()), |acc, t| acc + t);
+
+            vec![s * (sum - cur)]
+        });
+
+        WindowConfig {
+            term,
+            weights,
+            selector,
+            instance,
+        }
+    }
+
+    /// Reads the `W` seeds from instance rows 0..W and runs the recurrence to term `size`,
+    /// returning its cell
+    pub fn assign_window(
+        &self,
+        mut layouter: impl Layouter<F>,
+        size: usize,
+        weights: [u64; W],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = &self.config;
+        if W == 0 || size <= W {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "window",
+            |mut region| {
+                let mut terms = Vec::with_capacity(size);
+                for row in 0..W {
+                    terms.push(region.assign_advice_from_instance(
+                        || "seed",
+                        config.instance,
+                        row,
+                        config.term,
+                        row,
+                    )?);
+                }
+
+                for row in W..size {
+                    config.selector.enable(&mut region, row)?;
+
+                    let mut next = Value::known(F::zero());
+                    for (i, (weight, column)) in weights.iter().zip(config.weights).enumerate() {
+                        let w = F::from(*weight);
+                        region.assign_fixed(|| "weight", column, row, || Value::known(w))?;
+                        next = next + terms[row - W + i].value().map(|t| *t * w);
+                    }
+                    terms.push(region.assign_advice(|| "term", config.term, row, || next)?);
+                }
+
+                terms.pop().ok_or(Error::Synthesis)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// Runs a `W`-term recurrence with the given weights from the seeds at instance rows 0..W
+/// and exposes term `size` at row W
+#[derive(Clone, Debug)]
+pub struct WindowCircuit<const W: usize> {
+    pub size: usize,
+    pub weights: [u64; W],
+}
+
+impl<const W: usize> Default for WindowCircuit<W> {
+    fn default() -> Self {
+        Self {
+            size: 0,
+            weights: [1; W],
+        }
+    }
+}
+
+impl<F: FieldExt, const W: usize> Circuit<F> for WindowCircuit<W> {
+    type Config = WindowConfig<W>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    // The weights are fixed-column values, so they are shape and stay
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let term = cs.advice_column();
+        let instance = cs.instance_column();
+
+        WindowChip::configure_window(term, instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = WindowChip::construct(config);
+        let out = chip.assign_window(layouter.namespace(|| "window"), self.size, self.weights)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, W)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    fn run<const W: usize>(weights: [u64; W], seeds: [u64; W], out: u64) -> MockProver<Fr> {
+        let circuit = WindowCircuit { size: 10, weights };
+        let mut instance: Vec<_> = seeds.into_iter().map(Fr::from).collect();
+        instance.push(Fr::from(out));
+        MockProver::run(5, &circuit, vec![instance]).unwrap()
+    }
+
+    #[test]
+    fn window_of_two() {
+        // Fibonacci: F_10 = 55
+        assert_satisfied_verbose(&run([1, 1], [1, 1], 55));
+        assert!(run([1, 1], [1, 1], 54).verify().is_err());
+
+        // Pell: 0, 1, 2, 5, 12, 29, 70, 169, 408, 985
+        assert_satisfied_verbose(&run([1, 2], [0, 1], 985));
+        assert_eq!(
+            compute_window(10, [Fr::from(0), Fr::from(1)], [1, 2]),
+            Fr::from(985)
+        );
+    }
+
+    #[test]
+    fn window_of_three() {
+        // Tribonacci: 0, 0, 1, 1, 2, 4, 7, 13, 24, 44
+        assert_satisfied_verbose(&run([1, 1, 1], [0, 0, 1], 44));
+        assert!(run([1, 1, 1], [0, 0, 1], 43).verify().is_err());
+        assert_eq!(
+            compute_window(10, [Fr::from(0), Fr::from(0), Fr::from(1)], [1, 1, 1]),
+            Fr::from(44)
+        );
+    }
+}
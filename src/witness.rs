@@ -0,0 +1,215 @@
+use crate::circuit::{precompute_sequence_par, FibCircuit, OUTPUT_ROW, SEED_ROWS};
+use crate::encoding::{decode_instance, encode_instance, take, take_array, Endianness};
+use crate::error::Error;
+use crate::prover::{prove_fib, ProofBundle, BUNDLE_VERSION};
+use crate::{FibChip, FibConfig};
+use halo2_base::halo2_proofs::{
+    circuit::*,
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{self, Circuit, ConstraintSystem, ProvingKey},
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+};
+use std::path::Path;
+
+/// `FibCircuit` laid out from a precomputed F_1..=F_n instead of adding up each row.
+///
+/// The regions, columns and gate are exactly `FibCircuit`'s, so it proves under a key
+/// generated for the matching `FibCircuit` and its proofs verify against that key's VK.
+#[derive(Clone, Debug, Default)]
+pub struct StoredWitnessCircuit {
+    pub circuit: FibCircuit,
+    /// F_1..=F_{fib_size}; empty for keygen
+    pub terms: Vec<Fr>,
+}
+
+impl StoredWitnessCircuit {
+    fn term(&self, i: usize) -> Value<Fr> {
+        self.terms
+            .get(i)
+            .map_or(Value::unknown(), |t| Value::known(*t))
+    }
+}
+
+impl Circuit<Fr> for StoredWitnessCircuit {
+    type Config = FibConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            circuit: self.circuit.clone(),
+            terms: vec![],
+        }
+    }
+
+    fn configure(cs: &mut ConstraintSystem<Fr>) -> Self::Config {
+        <FibCircuit as Circuit<Fr>>::configure(cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), plonk::Error> {
+        let fib_size = self.circuit.fib_size;
+        if fib_size < 3 {
+            return Err(plonk::Error::Synthesis);
+        }
+
+        let chip = FibChip::construct_with_layout(config, self.circuit.instance_layout.clone());
+        let (_, mut b, mut c) = chip.assign_first_row(layouter.namespace(|| "first row"))?;
+        for i in 3..fib_size {
+            let new_c =
+                chip.assign_row_with(layouter.namespace(|| "next_row"), &b, &c, self.term(i))?;
+            b = c;
+            c = new_c;
+        }
+
+        if self.circuit.expose_output {
+            chip.expose_public(layouter.namespace(|| "out"), &c, OUTPUT_ROW)?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes F_1..=F_n for `circuit` and the seeds in `instances` and writes it to `path`,
+/// together with the circuit's spec and the instances, as
+/// `[spec len: u32][spec JSON][instance len: u32][instance][terms]`, integers little-endian
+/// and the instance and terms as `encode_instance` writes them.
+pub fn compute_and_save_witness(
+    circuit: &FibCircuit,
+    instances: &[Vec<Fr>],
+    path: impl AsRef<Path>,
+) -> Result<(), Error> {
+    circuit.check_instances(instances)?;
+
+    let column = &instances[0];
+    let [a, b] = SEED_ROWS.map(|row| column[circuit.instance_layout.row(row)]);
+    let terms = precompute_sequence_par(circuit.fib_size, a, b);
+
+    let spec = serde_json::to_vec(&circuit.spec())
+        .map_err(|err| Error::InvalidEncoding(format!("spec: {err}")))?;
+    let instances = encode_instance(instances, Endianness::Little);
+
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&(spec.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&spec);
+    bytes.extend_from_slice(&(instances.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&instances);
+    bytes.extend_from_slice(&encode_instance(&[terms], Endianness::Little));
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Proves from a `compute_and_save_witness` file without recomputing the sequence. `pk` is
+/// the key for the `FibCircuit` the witness was computed for.
+pub fn prove_from_witness(
+    path: impl AsRef<Path>,
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+) -> Result<ProofBundle, Error> {
+    let bytes = std::fs::read(path)?;
+    let mut reader = bytes.as_slice();
+
+    let len = u32::from_le_bytes(take_array(&mut reader)?) as usize;
+    let spec = serde_json::from_slice(take(&mut reader, len)?)
+        .map_err(|err| Error::InvalidEncoding(format!("spec: {err}")))?;
+    let len = u32::from_le_bytes(take_array(&mut reader)?) as usize;
+    let instances = decode_instance(take(&mut reader, len)?, Endianness::Little)?;
+    let terms = decode_instance(reader, Endianness::Little)?
+        .pop()
+        .unwrap_or_default();
+
+    let circuit = FibCircuit::from_spec(&spec)?;
+    if terms.len() != circuit.fib_size {
+        return Err(Error::InvalidEncoding(format!(
+            "{} terms for fib_size {}",
+            terms.len(),
+            circuit.fib_size
+        )));
+    }
+    circuit.check_instances(&instances)?;
+
+    // A tampered file is reported here rather than as a failed synthesis or proof
+    let seeds = SEED_ROWS.map(|row| instances[0][circuit.instance_layout.row(row)]);
+    if !terms.starts_with(&seeds) {
+        return Err(Error::InvalidEncoding(
+            "terms do not start at the seeds".into(),
+        ));
+    }
+    if let Some(i) = (2..terms.len()).find(|&i| terms[i - 2] + terms[i - 1] != terms[i]) {
+        return Err(Error::InvalidEncoding(format!(
+            "term {} is not the sum of the two before it",
+            i + 1
+        )));
+    }
+
+    let proof = prove_fib(
+        params,
+        pk,
+        StoredWitnessCircuit { circuit, terms },
+        &instances,
+        false,
+    )?;
+    Ok(ProofBundle {
+        version: BUNDLE_VERSION,
+        k: params.k(),
+        spec,
+        instances,
+        proof,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prover::{gen_params, gen_pk, min_k_for, verify_fib};
+    use crate::InstanceLayout;
+
+    #[test]
+    fn prove_from_saved_witness() {
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            ..Default::default()
+        };
+        let instances = vec![vec![Fr::from(1), Fr::from(1), Fr::from(55)]];
+        let params = gen_params(min_k_for(circuit.fib_size));
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        let path = std::env::temp_dir().join(format!("gpu-test-witness-{}", std::process::id()));
+        compute_and_save_witness(&circuit, &instances, &path).unwrap();
+        let bundle = prove_from_witness(&path, &params, &pk).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(bundle.instances, instances);
+        verify_fib(&params, pk.get_vk(), &bundle.instances, &bundle.proof).unwrap();
+
+        let fresh = prove_fib(&params, &pk, circuit, &instances, false).unwrap();
+        verify_fib(&params, pk.get_vk(), &instances, &fresh).unwrap();
+
+        // The layout travels in the spec, so the seeds are found after the output
+        let circuit = FibCircuit {
+            fib_size: 10,
+            expose_output: true,
+            instance_layout: InstanceLayout::OutputFirst,
+            ..Default::default()
+        };
+        let instances = vec![vec![Fr::from(55), Fr::from(1), Fr::from(1)]];
+        let pk = gen_pk(&params, &circuit).unwrap();
+
+        compute_and_save_witness(&circuit, &instances, &path).unwrap();
+        let bundle = prove_from_witness(&path, &params, &pk).unwrap();
+        verify_fib(&params, pk.get_vk(), &bundle.instances, &bundle.proof).unwrap();
+
+        // F_10 bumped from 55 to 56 in the last 32 bytes is refused before proving
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 32;
+        bytes[last] += 1;
+        std::fs::write(&path, bytes).unwrap();
+        assert!(matches!(
+            prove_from_witness(&path, &params, &pk),
+            Err(Error::InvalidEncoding(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,246 @@
+use halo2_base::halo2_proofs::{circuit::*, halo2curves::FieldExt, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+// Instance layout: [n]
+const N_ROW: usize = 0;
+
+/// Greedy Zeckendorf decomposition of `n` over the distinct Fibonacci numbers
+/// 1, 2, 3, 5, ... (F_2 onwards), as selection bits smallest first. `None` if `len` terms do
+/// not reach `n`.
+pub fn zeckendorf_bits(mut n: u64, len: usize) -> Option<Vec<bool>> {
+    let mut fibs = vec![];
+    let (mut x, mut y) = (1u64, 2u64);
+    for _ in 0..len {
+        fibs.push(x);
+        (x, y) = (y, x.checked_add(y).unwrap_or(u64::MAX));
+    }
+
+    let mut bits = vec![false; len];
+    for (i, fib) in fibs.iter().enumerate().rev() {
+        if *fib <= n {
+            bits[i] = true;
+            n -= fib;
+        }
+    }
+    (n == 0).then_some(bits)
+}
+
+#[derive(Debug, Clone)]
+pub struct ZeckendorfConfig {
+    pub fib: Column<Advice>,
+    pub bit: Column<Advice>,
+    pub acc: Column<Advice>,
+    pub first: Selector,
+    pub step: Selector,
+    pub recur: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// Proves a public integer is the sum of a private set of non-consecutive Fibonacci numbers.
+///
+/// Row i holds the Fibonacci number F_{i+2}, generated in-circuit from the constants 1 and 2,
+/// the selection bit for it and the running total of the selected numbers. Neighbouring bits
+/// may not both be set, which by Zeckendorf's theorem leaves exactly one valid pattern.
+pub struct ZeckendorfChip<F: FieldExt> {
+    config: ZeckendorfConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ZeckendorfChip<F> {
+    pub fn construct(config: ZeckendorfConfig) -> ZeckendorfChip<F> {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure_zeckendorf(
+        instance: Column<Instance>,
+        cs: &mut ConstraintSystem<F>,
+    ) -> ZeckendorfConfig {
+        let fib = cs.advice_column();
+        let bit = cs.advice_column();
+        let acc = cs.advice_column();
+        let constant = cs.fixed_column();
+        let first = cs.selector();
+        let step = cs.selector();
+        let recur = cs.selector();
+
+        cs.enable_equality(fib);
+        cs.enable_equality(acc);
+        cs.enable_equality(instance);
+        cs.enable_constant(constant);
+
+        cs.create_gate("zeckendorf first", |cells| {
+            let s = cells.query_selector(first);
+            let fib = cells.query_advice(fib, Rotation::cur());
+            let bit = cells.query_advice(bit, Rotation::cur());
+            let acc = cells.query_advice(acc, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * bit.clone() * (one - bit.clone()),
+                s * (acc - bit * fib),
+            ]
+        });
+
+        cs.create_gate("zeckendorf step", |cells| {
+            let s = cells.query_selector(step);
+            let fib = cells.query_advice(fib, Rotation::cur());
+            let bit = cells.query_advice(bit, Rotation::cur());
+            let bit_prev = cells.query_advice(bit, Rotation::prev());
+            let acc = cells.query_advice(acc, Rotation::cur());
+            let acc_prev = cells.query_advice(acc, Rotation::prev());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                s.clone() * bit.clone() * (one - bit.clone()),
+                // No two consecutive Fibonacci numbers
+                s.clone() * bit.clone() * bit_prev,
+                s * (acc - acc_prev - bit * fib),
+            ]
+        });
+
+        cs.create_gate("zeckendorf recurrence", |cells| {
+            let s = cells.query_selector(recur);
+            let fib = cells.query_advice(fib, Rotation::cur());
+            let fib_1 = cells.query_advice(fib, Rotation::prev());
+            let fib_2 = cells.query_advice(fib, Rotation(-2));
+
+            vec![s * (fib - fib_1 - fib_2)]
+        });
+
+        ZeckendorfConfig {
+            fib,
+            bit,
+            acc,
+            first,
+            step,
+            recur,
+            instance,
+        }
+    }
+
+    /// Assigns one row per selection bit and returns the cell holding the selected total
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bits: &[Value<bool>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = &self.config;
+        if bits.len() < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        layouter.assign_region(
+            || "zeckendorf",
+            |mut region| {
+                let mut fibs = vec![
+                    region.assign_advice_from_constant(|| "F_2", config.fib, 0, F::one())?,
+                    region.assign_advice_from_constant(|| "F_3", config.fib, 1, F::from(2))?,
+                ];
+                let mut acc = Value::known(F::zero());
+                let mut acc_cell = None;
+
+                for (row, bit) in bits.iter().enumerate() {
+                    if row == 0 {
+                        config.first.enable(&mut region, row)?;
+                    } else {
+                        config.step.enable(&mut region, row)?;
+                    }
+                    if row >= 2 {
+                        config.recur.enable(&mut region, row)?;
+                        let value = fibs[row - 1].value().copied() + fibs[row - 2].value();
+                        fibs.push(region.assign_advice(|| "fib", config.fib, row, || value)?);
+                    }
+
+                    let bit = bit.map(|bit| if bit { F::one() } else { F::zero() });
+                    region.assign_advice(|| "bit", config.bit, row, || bit)?;
+
+                    acc = acc + bit * fibs[row].value();
+                    acc_cell = Some(region.assign_advice(|| "acc", config.acc, row, || acc)?);
+                }
+
+                acc_cell.ok_or(Error::Synthesis)
+            },
+        )
+    }
+
+    pub fn expose_n(
+        &self,
+        mut layouter: impl Layouter<F>,
+        total: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(total.cell(), self.config.instance, N_ROW)
+    }
+}
+
+/// Instance: [n]. The bits select from 1, 2, 3, 5, ...; their count fixes the circuit shape.
+#[derive(Clone, Debug, Default)]
+pub struct ZeckendorfCircuit {
+    pub bits: Vec<Value<bool>>,
+}
+
+impl ZeckendorfCircuit {
+    pub fn new(bits: &[bool]) -> Self {
+        Self {
+            bits: bits.iter().copied().map(Value::known).collect(),
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for ZeckendorfCircuit {
+    type Config = ZeckendorfConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            bits: vec![Value::unknown(); self.bits.len()],
+        }
+    }
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let instance = cs.instance_column();
+        ZeckendorfChip::configure_zeckendorf(instance, cs)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = ZeckendorfChip::construct(config);
+
+        let total = chip.assign(layouter.namespace(|| "zeckendorf"), &self.bits)?;
+        chip.expose_n(layouter.namespace(|| "n"), &total)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::assert_satisfied_verbose;
+    use halo2_base::halo2_proofs::dev::MockProver;
+    use halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+
+    #[test]
+    fn hundred() {
+        // 1, 2, 3, 5, 8, 13, 21, 34, 55, 89: 100 = 89 + 8 + 3
+        let bits = [
+            false, false, true, false, true, false, false, false, false, true,
+        ];
+        assert_eq!(zeckendorf_bits(100, 10).unwrap(), bits);
+
+        let circuit = ZeckendorfCircuit::new(&bits);
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![vec![Fr::from(100)]]).unwrap();
+        assert_satisfied_verbose(&prover);
+
+        // 89 + 8 + 2 + 1 also makes 100, but picks the consecutive 1 and 2
+        let bits = [
+            true, true, false, false, true, false, false, false, false, true,
+        ];
+        let circuit = ZeckendorfCircuit::new(&bits);
+        let prover = MockProver::<Fr>::run(5, &circuit, vec![vec![Fr::from(100)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}